@@ -0,0 +1,112 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use ethereum_abi::{Abi, DecodeLayout, Type, Value};
+use ethereum_types::U256;
+
+const INPUT: &str = "a1671295000000000000000000000000a0b211418d87c9f5918e6213fec3b13290aa5f26000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20000000000000000000000000000000000000000000000000000000000000bb8";
+
+fn decode_input(c: &mut Criterion) {
+    let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+        .expect("failed to parse ABI");
+    let input = hex::decode(INPUT).expect("invalid hex input");
+
+    let mut group = c.benchmark_group("decode_input_from_slice");
+
+    group.bench_function("abi", |b| {
+        b.iter(|| abi.decode_input_from_slice(&input).unwrap())
+    });
+
+    let index = abi.index();
+    group.bench_function("abi_index", |b| {
+        b.iter(|| index.decode_input_from_slice(&input).unwrap())
+    });
+
+    group.finish();
+}
+
+// Decodes an array of 8 word records, as static tuples and as fixed arrays, along
+// with the same words decoded as individual values.
+fn decode_static_records(c: &mut Criterion) {
+    const RECORDS: usize = 1000;
+
+    let words: Vec<_> = (0..8u64).map(|i| Value::Uint(U256::from(i), 256)).collect();
+    let tuple_ty = Type::Tuple(
+        (0..8)
+            .map(|i| (format!("field{}", i), Type::Uint(256)))
+            .collect(),
+    );
+    let fixed_array_ty = Type::FixedArray(Box::new(Type::Uint(256)), 8);
+
+    let record = Value::Tuple(
+        words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| (format!("field{}", i), word.clone()))
+            .collect(),
+    );
+    let records = Value::encode(&[Value::Array(vec![record; RECORDS], tuple_ty.clone())]);
+
+    let word_tys = vec![Type::Uint(256); 8 * RECORDS];
+    let flat_words = Value::encode(&vec![Value::Uint(U256::one(), 256); 8 * RECORDS]);
+
+    let mut group = c.benchmark_group("decode_static_records");
+
+    group.bench_function("tuple_array", |b| {
+        let tys = [Type::Array(Box::new(tuple_ty.clone()))];
+        b.iter(|| Value::decode_from_slice(&records, &tys).unwrap())
+    });
+
+    group.bench_function("fixed_array_array", |b| {
+        let tys = [Type::Array(Box::new(fixed_array_ty.clone()))];
+        b.iter(|| Value::decode_from_slice(&records, &tys).unwrap())
+    });
+
+    group.bench_function("words", |b| {
+        b.iter(|| Value::decode_from_slice(&flat_words, &word_tys).unwrap())
+    });
+
+    group.finish();
+}
+
+// Decodes the same arguments over and over, with and without a precomputed layout:
+// the createPool arguments and a deeply nested schema whose types are expensive to
+// walk.
+fn decode_layout(c: &mut Criterion) {
+    let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+        .expect("failed to parse ABI");
+    let input = hex::decode(INPUT).expect("invalid hex input");
+    let (f, args) = abi.split_input(&input).unwrap();
+    let tys: Vec<_> = f.inputs.iter().map(|input| input.type_.clone()).collect();
+
+    let nested_ty = (0..8).fold(Type::Uint(256), |ty, i| {
+        Type::Tuple(vec![
+            (format!("a{}", i), ty),
+            (format!("b{}", i), Type::Address),
+        ])
+    });
+    let nested_tys = vec![nested_ty; 4];
+    let nested_values: Vec<_> = nested_tys.iter().map(Value::default_for).collect();
+    let nested_input = Value::encode(&nested_values);
+
+    let mut group = c.benchmark_group("decode_layout");
+
+    group.bench_function("decode_from_slice", |b| {
+        b.iter(|| Value::decode_from_slice(args, &tys).unwrap())
+    });
+
+    let layout = DecodeLayout::precompute(&tys);
+    group.bench_function("precomputed", |b| b.iter(|| layout.decode(args).unwrap()));
+
+    group.bench_function("nested_decode_from_slice", |b| {
+        b.iter(|| Value::decode_from_slice(&nested_input, &nested_tys).unwrap())
+    });
+
+    let nested_layout = DecodeLayout::precompute(&nested_tys);
+    group.bench_function("nested_precomputed", |b| {
+        b.iter(|| nested_layout.decode(&nested_input).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, decode_input, decode_static_records, decode_layout);
+criterion_main!(benches);
@@ -1,16 +1,11 @@
-use std::{fs::File, str::FromStr};
+use std::str::FromStr;
 
-use ethereum_abi::Abi;
+use ethereum_abi::{hex_to_bytes, Abi};
 use ethereum_types::H256;
 
 fn main() {
     // Parse ABI JSON file
-    let abi: Abi = {
-        let file =
-            File::open("examples/uniswapv3factory_abi.json").expect("failed to open ABI file");
-
-        serde_json::from_reader(file).expect("failed to parse ABI")
-    };
+    let abi = Abi::from_file("examples/uniswapv3factory_abi.json").expect("failed to load ABI");
 
     // Log data
     // Taken from: https://etherscan.io/tx/0x535e880ab0d966fbc7a354c322046fe6f01581e94b0d9b76a12683feefb98481#eventlog
@@ -21,11 +16,11 @@ fn main() {
         H256::from_str("0000000000000000000000000000000000000000000000000000000000000bb8").unwrap(),
     ];
 
-    let data = "000000000000000000000000000000000000000000000000000000000000003c000000000000000000000000acabbea9c2d0ff835418d139d6a570b5025be085".as_bytes();
+    let data = hex_to_bytes("0x000000000000000000000000000000000000000000000000000000000000003c000000000000000000000000acabbea9c2d0ff835418d139d6a570b5025be085").expect("invalid log data");
 
     // Decode
     let (evt, decoded_data) = abi
-        .decode_log_from_slice(&topics, data)
+        .decode_log_from_slice(&topics, &data)
         .expect("failed decoding log");
 
     println!("event: {}\ndata: {:?}", evt.name, decoded_data);
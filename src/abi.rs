@@ -1,8 +1,23 @@
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use anyhow::{anyhow, Result};
 use ethereum_types::H256;
-use serde::{de::Visitor, Deserialize, Serialize};
-
-use crate::{params::Param, DecodedParams, Error, Event, Value};
+use serde::{
+    de::{value::MapAccessDeserializer, DeserializeSeed, Deserializer as _, Visitor},
+    Deserialize, Serialize,
+};
+
+use crate::{
+    human_readable,
+    keccak::{self, Keccak256, TinyKeccak},
+    params::{Param, ParamEntry},
+    util, AbiError, DecodedParams, Error, Event, RevertReason, Type, Value, DEFAULT_MAX_DEPTH,
+};
 
 /// Contract ABI (Abstract Binary Interface).
 ///
@@ -29,58 +44,406 @@ pub struct Abi {
     pub events: Vec<Event>,
     /// Contract defined errors.
     pub errors: Vec<Error>,
-    /// Whether the contract has the receive method defined.
-    pub has_receive: bool,
-    /// Whether the contract has the fallback method defined.
-    pub has_fallback: bool,
+    /// Contract receive method state mutability (if it defines one).
+    pub receive: Option<StateMutability>,
+    /// Contract fallback method state mutability (if it defines one).
+    pub fallback: Option<StateMutability>,
 }
 
 impl Abi {
+    /// Parses an ABI from a JSON string using the given parsing options.
+    ///
+    /// Invalid JSON is reported as [`AbiError::Json`].
+    pub fn from_str_with(s: &str, opts: &ParseOptions) -> Result<Abi> {
+        Self::from_str_with_fixups(s, opts).map(|(abi, _)| abi)
+    }
+
+    /// Parses an ABI from a JSON string using the given parsing options, returning
+    /// the fixups made to accept it.
+    ///
+    /// Non strict parsing rounds `int<M>`/`uint<M>` param sizes that are not
+    /// multiples of 8 up to the next multiple, e.g. `uint7` to `uint8`. Such sizes
+    /// are invalid and would compute a selector no contract matches, so strict
+    /// parsing rejects them instead.
+    pub fn from_str_with_fixups(s: &str, opts: &ParseOptions) -> Result<(Abi, Vec<TypeFixup>)> {
+        Self::parse(s, opts).map(|(abi, fixups, _)| (abi, fixups))
+    }
+
+    /// Parses an ABI from a JSON string using the given parsing options, returning
+    /// the precomputed signatures its function and event entries came with.
+    ///
+    /// Strict parsing rejects signatures that don't match the computed ones, while
+    /// non strict parsing only reports them through [`ProvidedSignature::matches`].
+    pub fn from_str_with_signatures(
+        s: &str,
+        opts: &ParseOptions,
+    ) -> Result<(Abi, Vec<ProvidedSignature>)> {
+        Self::parse(s, opts).map(|(abi, _, signatures)| (abi, signatures))
+    }
+
+    fn parse(s: &str, opts: &ParseOptions) -> Result<ParsedAbi> {
+        let mut deserializer = serde_json::Deserializer::from_str(s);
+
+        let parsed = deserializer
+            .deserialize_seq(AbiVisitor { opts: *opts })
+            .map_err(AbiError::from)?;
+        deserializer.end().map_err(AbiError::from)?;
+
+        Ok(parsed)
+    }
+
+    /// Reads and parses an ABI JSON file.
+    ///
+    /// Invalid JSON is reported as [`AbiError::Json`], and read failures as the
+    /// underlying [`std::io::Error`], both recoverable with
+    /// [`anyhow::Error::downcast_ref`].
+    #[cfg(feature = "std")]
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Abi> {
+        use anyhow::Context;
+
+        let path = path.as_ref();
+
+        let s = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read ABI file {}", path.display()))?;
+
+        Self::from_str_with(&s, &ParseOptions::default())
+            .with_context(|| format!("failed to parse ABI file {}", path.display()))
+    }
+
+    /// Parses an ABI from a build artifact JSON document, such as the ones produced by
+    /// Hardhat or Truffle, where the ABI is found under the `abi` key.
+    pub fn from_artifact_str(s: &str) -> Result<Abi> {
+        #[derive(Deserialize)]
+        struct Artifact {
+            abi: Abi,
+        }
+
+        let artifact: Artifact = serde_json::from_str(s).map_err(AbiError::from)?;
+
+        Ok(artifact.abi)
+    }
+
+    /// Parses an ABI from human readable Solidity declarations, one per line.
+    ///
+    /// Supports `function`, `event`, `error`, `constructor`, `receive` and `fallback`
+    /// declarations, with optional param names, `indexed` event params, tuple params
+    /// written as `(uint256 a, bool b)`, state mutability modifiers and `returns`.
+    ///
+    /// ```
+    /// use ethereum_abi::Abi;
+    ///
+    /// let abi = Abi::from_human_readable(&[
+    ///     "function transfer(address to, uint256 amount) returns (bool)",
+    ///     "event Transfer(address indexed from, address indexed to, uint256 value)",
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_eq!(abi.functions[0].signature(), "transfer(address,uint256)");
+    /// assert_eq!(abi.events[0].signature(), "Transfer(address,address,uint256)");
+    /// ```
+    pub fn from_human_readable(lines: &[&str]) -> Result<Abi> {
+        human_readable::parse(lines)
+    }
+
+    /// Returns whether the contract has the receive method defined.
+    pub fn has_receive(&self) -> bool {
+        self.receive.is_some()
+    }
+
+    /// Returns whether the contract has the fallback method defined.
+    pub fn has_fallback(&self) -> bool {
+        self.fallback.is_some()
+    }
+
+    /// Returns the number of functions.
+    pub fn function_count(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Returns the number of events.
+    pub fn event_count(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns the number of errors.
+    pub fn error_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns summary stats of the ABI.
+    pub fn summary(&self) -> AbiSummary {
+        let count = |state_mutability| {
+            self.functions
+                .iter()
+                .filter(|f| f.state_mutability == state_mutability)
+                .count()
+        };
+
+        AbiSummary {
+            pure_functions: count(StateMutability::Pure),
+            view_functions: count(StateMutability::View),
+            nonpayable_functions: count(StateMutability::NonPayable),
+            payable_functions: count(StateMutability::Payable),
+            events: self.event_count(),
+            errors: self.error_count(),
+            has_constructor: self.constructor.is_some(),
+            has_receive: self.has_receive(),
+            has_fallback: self.has_fallback(),
+        }
+    }
+
+    /// Returns whether the contract defines a function with the given name, or with
+    /// the given signature if it contains parentheses, e.g. `transfer(address,uint256)`.
+    ///
+    /// A bare name matches any of the function's overloads.
+    pub fn has_function(&self, name_or_signature: &str) -> bool {
+        self.functions
+            .iter()
+            .any(|f| matches_name_or_signature(&f.name, || f.signature(), name_or_signature))
+    }
+
+    /// Returns whether the contract defines an event with the given name or signature.
+    ///
+    /// See [`Abi::has_function`].
+    pub fn has_event(&self, name_or_signature: &str) -> bool {
+        self.events
+            .iter()
+            .any(|e| matches_name_or_signature(&e.name, || e.signature(), name_or_signature))
+    }
+
+    /// Returns whether the contract defines an error with the given name or signature.
+    ///
+    /// See [`Abi::has_function`].
+    pub fn has_error(&self, name_or_signature: &str) -> bool {
+        self.errors
+            .iter()
+            .any(|e| matches_name_or_signature(&e.name, || e.signature(), name_or_signature))
+    }
+
+    /// Decodes a standard `Error(string)` or `Panic(uint256)` revert reason from the
+    /// return data of a reverted call.
+    ///
+    /// This is the same as [`RevertReason::decode`].
+    pub fn decode_revert(data: &[u8]) -> Option<RevertReason> {
+        RevertReason::decode(data)
+    }
+
+    /// Builds the `topics` filter of an `eth_getLogs` request for the event with the
+    /// given name.
+    ///
+    /// `indexed` holds the values to match for the event's indexed params, with `None`
+    /// matching any value (see [`Event::filter_topics`]). Topics are `0x` prefixed hex
+    /// strings, and wildcards are `null`. Overloaded events are told apart by their
+    /// number of indexed params.
+    pub fn event_filter(&self, name: &str, indexed: &[Option<Value>]) -> Result<serde_json::Value> {
+        let events = self.events_by_name(name);
+
+        let event = match events.as_slice() {
+            [] => return Err(anyhow!("ABI event not found: {}", name)),
+            [event] => *event,
+            _ => {
+                let mut candidates = events
+                    .iter()
+                    .filter(|e| e.indexed_inputs().count() == indexed.len());
+
+                match (candidates.next(), candidates.next()) {
+                    (Some(event), None) => *event,
+                    _ => return Err(anyhow!("ambiguous ABI event name: {}", name)),
+                }
+            }
+        };
+
+        let topics = event
+            .filter_topics(indexed)?
+            .into_iter()
+            .map(|topic| match topic {
+                Some(topic) => serde_json::Value::String(format!("{:?}", topic)),
+                None => serde_json::Value::Null,
+            })
+            .collect();
+
+        Ok(serde_json::Value::Array(topics))
+    }
+
+    /// Returns the functions that don't modify the blockchain state (`view` or
+    /// `pure`).
+    pub fn read_functions(&self) -> Vec<&Function> {
+        self.functions.iter().filter(|f| f.is_read_only()).collect()
+    }
+
+    /// Returns the functions that may modify the blockchain state (`payable` or
+    /// `nonpayable`).
+    pub fn write_functions(&self) -> Vec<&Function> {
+        self.functions.iter().filter(|f| f.is_mutating()).collect()
+    }
+
+    /// Returns the signatures of all functions, e.g. `transfer(address,uint256)`.
+    pub fn function_signatures(&self) -> Vec<String> {
+        self.functions.iter().map(Function::signature).collect()
+    }
+
+    /// Returns the signatures of all events.
+    pub fn event_signatures(&self) -> Vec<String> {
+        self.events.iter().map(Event::signature).collect()
+    }
+
+    /// Returns the signatures of all errors.
+    pub fn error_signatures(&self) -> Vec<String> {
+        self.errors.iter().map(Error::signature).collect()
+    }
+
+    /// Returns an iterator over the selectors (method ids) of all functions along
+    /// with the functions themselves.
+    pub fn selectors(&self) -> impl Iterator<Item = ([u8; 4], &Function)> {
+        self.functions.iter().map(|f| (f.method_id(), f))
+    }
+
+    /// Returns a map from the selector (method id) of each function to its
+    /// signature, e.g. for annotating call data.
+    #[cfg(feature = "std")]
+    pub fn selector_map(&self) -> std::collections::HashMap<[u8; 4], String> {
+        self.functions
+            .iter()
+            .map(|f| (f.method_id(), f.signature()))
+            .collect()
+    }
+
+    /// Returns an iterator over the topic hashes of all non anonymous events along
+    /// with the events themselves.
+    pub fn topics(&self) -> impl Iterator<Item = (H256, &Event)> {
+        self.events
+            .iter()
+            .filter(|e| !e.anonymous)
+            .map(|e| (e.topic(), e))
+    }
+
+    /// Builds lookup tables with the selectors and topics of this ABI precomputed.
+    pub fn index(&self) -> AbiIndex<'_> {
+        AbiIndex::new(self)
+    }
+
+    /// Returns the function with the given selector (method id), if any.
+    pub fn function_by_selector(&self, selector: [u8; 4]) -> Option<&Function> {
+        self.selectors()
+            .find(|(method_id, _)| *method_id == selector)
+            .map(|(_, f)| f)
+    }
+
+    /// Returns the non anonymous event with the given topic hash, if any.
+    ///
+    /// Anonymous events are never matched since they do not emit their topic hash.
+    pub fn event_by_topic(&self, topic: H256) -> Option<&Event> {
+        self.topics().find(|(t, _)| *t == topic).map(|(_, e)| e)
+    }
+
+    /// Returns all events with the given name.
+    ///
+    /// Events may be overloaded, so more than one event can be returned. Use
+    /// [`Abi::event_by_topic`] to match an event precisely.
+    pub fn events_by_name(&self, name: &str) -> Vec<&Event> {
+        self.events.iter().filter(|e| e.name == name).collect()
+    }
+
+    /// Splits function call data into the called function and its undecoded
+    /// argument bytes (everything after the selector).
+    pub fn split_input<'a>(&self, input: &'a [u8]) -> Result<(&Function, &'a [u8])> {
+        split_input_with(input, |selector| self.function_by_selector(selector))
+    }
+
     // Decode function input from slice.
     pub fn decode_input_from_slice<'a>(
         &'a self,
         input: &[u8],
     ) -> Result<(&'a Function, DecodedParams)> {
-        let f = self
-            .functions
-            .iter()
-            .find(|f| f.method_id() == input[0..4])
-            .ok_or_else(|| anyhow!("ABI function not found"))?;
+        let (f, args) = self.split_input(input)?;
 
-        let decoded_params = f.decode_input_from_slice(&input[4..])?;
+        let decoded_params = f.decode_input_from_slice(args)?;
 
         Ok((f, decoded_params))
     }
 
-    // Decode function input from hex string.
+    /// Decodes each of the given call data blobs, e.g. the inner calls of a multicall.
+    ///
+    /// Every call is decoded independently, so a call that fails to decode doesn't
+    /// prevent decoding the others.
+    pub fn decode_multicall<B: AsRef<[u8]>>(
+        &self,
+        calls: &[B],
+    ) -> Vec<Result<(&Function, DecodedParams)>> {
+        calls
+            .iter()
+            .map(|call| self.decode_input_from_slice(call.as_ref()))
+            .collect()
+    }
+
+    // Decode function input from hex string, with or without a `0x` prefix.
+    // Surrounding whitespace is ignored.
     pub fn decode_input_from_hex<'a>(
         &'a self,
         input: &str,
     ) -> Result<(&'a Function, DecodedParams)> {
-        let slice = hex::decode(input)?;
+        let slice = util::hex_to_bytes(input)?;
 
         self.decode_input_from_slice(&slice)
     }
 
+    /// Decode function input from a hex string into a JSON document of the form
+    /// `{"function":"transfer","signature":"transfer(address,uint256)","args":[{"name":"to","type":"address","value":"0x..."}, ...]}`,
+    /// where argument values are formatted by [`Value::to_json`].
+    pub fn decode_input_to_json(&self, input: &str) -> Result<String> {
+        let (f, decoded_params) = self.decode_input_from_hex(input)?;
+
+        let args: Vec<_> = decoded_params
+            .iter()
+            .map(|decoded_param| {
+                serde_json::json!({
+                    "name": decoded_param.param.name,
+                    "type": decoded_param.param.type_.to_string(),
+                    "value": decoded_param.value.to_json(),
+                })
+            })
+            .collect();
+
+        Ok(serde_json::json!({
+            "function": f.name,
+            "signature": f.signature(),
+            "args": args,
+        })
+        .to_string())
+    }
+
+    /// Decode a transaction's input from a hex string, with or without a `0x` prefix.
+    /// Surrounding whitespace is ignored.
+    ///
+    /// Returns `None` for empty input, e.g. plain Ether transfers. When no function
+    /// matches the input's selector, the error downcasts to
+    /// [`AbiError::UnknownSelector`], which carries the selector.
+    pub fn decode_transaction_input<'a>(&'a self, input: &str) -> Result<Option<DecodedCall<'a>>> {
+        let input = util::hex_to_bytes(input)?;
+
+        if input.is_empty() {
+            return Ok(None);
+        }
+
+        let (function, params) = self.decode_input_from_slice(&input)?;
+
+        let mut selector = [0u8; 4];
+        selector.copy_from_slice(&input[0..4]);
+
+        Ok(Some(DecodedCall {
+            function,
+            selector,
+            params,
+        }))
+    }
+
     /// Decode event data from slice.
     pub fn decode_log_from_slice<'a>(
         &'a self,
         topics: &[H256],
         data: &[u8],
     ) -> Result<(&'a Event, DecodedParams)> {
-        if topics.is_empty() {
-            return Err(anyhow!("missing event topic id"));
-        }
-
-        let e = self
-            .events
-            .iter()
-            .find(|e| e.topic() == topics[0])
-            .ok_or_else(|| anyhow!("ABI event not found"))?;
-
-        let decoded_params = e.decode_data_from_slice(topics, data)?;
-
-        Ok((e, decoded_params))
+        decode_log_with(topics, data, |topic| self.event_by_topic(topic))
     }
 }
 
@@ -95,10 +458,13 @@ impl Serialize for Abi {
             entries.push(AbiEntry {
                 type_: String::from("constructor"),
                 name: None,
-                inputs: Some(c.inputs.clone()),
+                inputs: Some(param_entries(&c.inputs)),
                 outputs: None,
                 state_mutability: Some(StateMutability::NonPayable),
                 anonymous: None,
+                constant: None,
+                payable: None,
+                signature: None,
             });
         }
 
@@ -106,10 +472,13 @@ impl Serialize for Abi {
             entries.push(AbiEntry {
                 type_: String::from("function"),
                 name: Some(f.name.clone()),
-                inputs: Some(f.inputs.clone()),
-                outputs: Some(f.outputs.clone()),
+                inputs: Some(param_entries(&f.inputs)),
+                outputs: Some(param_entries(&f.outputs)),
                 state_mutability: Some(f.state_mutability),
                 anonymous: None,
+                constant: None,
+                payable: None,
+                signature: None,
             });
         }
 
@@ -117,10 +486,13 @@ impl Serialize for Abi {
             entries.push(AbiEntry {
                 type_: String::from("event"),
                 name: Some(e.name.clone()),
-                inputs: Some(e.inputs.clone()),
+                inputs: Some(param_entries(&e.inputs)),
                 outputs: None,
                 state_mutability: None,
                 anonymous: Some(e.anonymous),
+                constant: None,
+                payable: None,
+                signature: None,
             });
         }
 
@@ -128,32 +500,41 @@ impl Serialize for Abi {
             entries.push(AbiEntry {
                 type_: String::from("error"),
                 name: Some(e.name.clone()),
-                inputs: Some(e.inputs.clone()),
+                inputs: Some(param_entries(&e.inputs)),
                 outputs: None,
                 state_mutability: None,
                 anonymous: None,
+                constant: None,
+                payable: None,
+                signature: None,
             });
         }
 
-        if self.has_receive {
+        if let Some(state_mutability) = self.receive {
             entries.push(AbiEntry {
                 type_: String::from("receive"),
                 name: None,
                 inputs: None,
                 outputs: None,
-                state_mutability: Some(StateMutability::Payable),
+                state_mutability: Some(state_mutability),
                 anonymous: None,
+                constant: None,
+                payable: None,
+                signature: None,
             });
         }
 
-        if self.has_fallback {
+        if let Some(state_mutability) = self.fallback {
             entries.push(AbiEntry {
                 type_: String::from("fallback"),
                 name: None,
                 inputs: None,
                 outputs: None,
-                state_mutability: Some(StateMutability::Payable),
+                state_mutability: Some(state_mutability),
                 anonymous: None,
+                constant: None,
+                payable: None,
+                signature: None,
             });
         }
 
@@ -166,8 +547,226 @@ impl<'de> Deserialize<'de> for Abi {
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_seq(AbiVisitor)
+        deserializer
+            .deserialize_seq(AbiVisitor {
+                opts: ParseOptions::default(),
+            })
+            .map(|(abi, _, _)| abi)
+    }
+}
+
+impl core::str::FromStr for Abi {
+    type Err = anyhow::Error;
+
+    /// Parses an ABI from a JSON string using the default parsing options.
+    ///
+    /// Invalid JSON is reported as [`AbiError::Json`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with(s, &ParseOptions::default())
+    }
+}
+
+/// Options controlling how ABI JSON is parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Whether to reject ABI entries of unknown types, function or event entries
+    /// whose precomputed `signature` field doesn't match the computed signature, and
+    /// int sizes that are not multiples of 8.
+    ///
+    /// When disabled (the default), unknown entries are skipped so that ABIs with
+    /// newer entry kinds can still be parsed, mismatching `signature` fields are
+    /// only reported (see [`Abi::from_str_with_signatures`]), and invalid int sizes
+    /// are rounded up (see [`Abi::from_str_with_fixups`]).
+    pub strict: bool,
+    /// Maximum nesting depth of parameter types (see [`Type::depth`]).
+    ///
+    /// Defaults to [`DEFAULT_MAX_DEPTH`].
+    pub max_depth: usize,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Options that reject anything the parser doesn't understand.
+    pub fn strict() -> Self {
+        Self {
+            strict: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// A param type rewritten to accept an invalid ABI, see [`Abi::from_str_with_fixups`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeFixup {
+    /// Type of the ABI entry holding the param, e.g. `function`.
+    pub entry_type: String,
+    /// Name of the ABI entry holding the param, if it has one.
+    pub entry_name: Option<String>,
+    /// The param type as given, e.g. `uint7[]`.
+    pub from: String,
+    /// The type it was replaced with, e.g. `uint8[]`.
+    pub to: String,
+}
+
+/// A precomputed signature an ABI entry came with, see
+/// [`Abi::from_str_with_signatures`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvidedSignature {
+    /// Type of the ABI entry, `function` or `event`.
+    pub entry_type: String,
+    /// Name of the ABI entry.
+    pub entry_name: String,
+    /// The signature as given, either the signature string or the hex encoded
+    /// selector or topic hash.
+    pub signature: String,
+    /// Whether the signature matches the one computed from the entry.
+    pub matches: bool,
+}
+
+/// Summary stats of an [`Abi`], see [`Abi::summary`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AbiSummary {
+    /// Number of `pure` functions.
+    pub pure_functions: usize,
+    /// Number of `view` functions.
+    pub view_functions: usize,
+    /// Number of `nonpayable` functions.
+    pub nonpayable_functions: usize,
+    /// Number of `payable` functions.
+    pub payable_functions: usize,
+    /// Number of events.
+    pub events: usize,
+    /// Number of errors.
+    pub errors: usize,
+    /// Whether the contract has a constructor defined.
+    pub has_constructor: bool,
+    /// Whether the contract has the receive method defined.
+    pub has_receive: bool,
+    /// Whether the contract has the fallback method defined.
+    pub has_fallback: bool,
+}
+
+impl AbiSummary {
+    /// Returns the total number of functions.
+    pub fn functions(&self) -> usize {
+        self.pure_functions
+            + self.view_functions
+            + self.nonpayable_functions
+            + self.payable_functions
+    }
+}
+
+/// Decoded contract function call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedCall<'a> {
+    /// Called function.
+    pub function: &'a Function,
+    /// Called function selector.
+    pub selector: [u8; 4],
+    /// Decoded function arguments.
+    pub params: DecodedParams,
+}
+
+/// Precomputed function selector and event topic lookup tables for an [`Abi`].
+///
+/// Looking up functions and events in an [`Abi`] hashes every candidate signature
+/// on each call. An index hashes them once, which pays off when decoding many
+/// transactions or logs against the same ABI.
+#[derive(Debug, Clone)]
+pub struct AbiIndex<'a> {
+    functions: BTreeMap<[u8; 4], &'a Function>,
+    events: BTreeMap<H256, &'a Event>,
+}
+
+impl<'a> AbiIndex<'a> {
+    /// Builds the lookup tables for the given ABI.
+    pub fn new(abi: &'a Abi) -> Self {
+        let mut functions = BTreeMap::new();
+        for (selector, f) in abi.selectors() {
+            functions.entry(selector).or_insert(f);
+        }
+
+        let mut events = BTreeMap::new();
+        for (topic, e) in abi.topics() {
+            events.entry(topic).or_insert(e);
+        }
+
+        Self { functions, events }
+    }
+
+    /// Returns the function with the given selector (method id), if any.
+    pub fn function_by_selector(&self, selector: [u8; 4]) -> Option<&'a Function> {
+        self.functions.get(&selector).copied()
+    }
+
+    /// Returns the non anonymous event with the given topic hash, if any.
+    pub fn event_by_topic(&self, topic: H256) -> Option<&'a Event> {
+        self.events.get(&topic).copied()
+    }
+
+    /// Decode function input from slice.
+    pub fn decode_input_from_slice(&self, input: &[u8]) -> Result<(&'a Function, DecodedParams)> {
+        let (f, args) = split_input_with(input, |selector| self.function_by_selector(selector))?;
+
+        let decoded_params = f.decode_input_from_slice(args)?;
+
+        Ok((f, decoded_params))
+    }
+
+    /// Decode event data from slice.
+    pub fn decode_log_from_slice(
+        &self,
+        topics: &[H256],
+        data: &[u8],
+    ) -> Result<(&'a Event, DecodedParams)> {
+        decode_log_with(topics, data, |topic| self.event_by_topic(topic))
+    }
+}
+
+// Splits function call data into the called function, found with the given lookup,
+// and its undecoded argument bytes.
+fn split_input_with<'f, 'a>(
+    input: &'a [u8],
+    function_by_selector: impl FnOnce([u8; 4]) -> Option<&'f Function>,
+) -> Result<(&'f Function, &'a [u8])> {
+    if input.len() < 4 {
+        return Err(anyhow!("missing function selector"));
+    }
+
+    let (selector_bytes, args) = input.split_at(4);
+
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(selector_bytes);
+
+    let f = function_by_selector(selector).ok_or(AbiError::UnknownSelector { selector })?;
+
+    Ok((f, args))
+}
+
+// Decodes event data, finding the event by the log's first topic with the given
+// lookup.
+fn decode_log_with<'e>(
+    topics: &[H256],
+    data: &[u8],
+    event_by_topic: impl FnOnce(H256) -> Option<&'e Event>,
+) -> Result<(&'e Event, DecodedParams)> {
+    if topics.is_empty() {
+        return Err(anyhow!("missing event topic id"));
     }
+
+    let e = event_by_topic(topics[0]).ok_or_else(|| anyhow!("ABI event not found"))?;
+
+    let decoded_params = e.decode_data_from_slice(topics, data)?;
+
+    Ok((e, decoded_params))
 }
 
 /// Contract constructor definition.
@@ -193,19 +792,62 @@ pub struct Function {
 }
 
 impl Function {
-    /// Computes the function's method id (function selector).
-    pub fn method_id(&self) -> [u8; 4] {
-        use tiny_keccak::{Hasher, Keccak};
+    /// Creates a nonpayable function with the given name and no inputs or outputs.
+    ///
+    /// ```
+    /// use ethereum_abi::{Function, StateMutability, Type};
+    ///
+    /// let f = Function::new("balanceOf")
+    ///     .input("owner", Type::Address)
+    ///     .output("", Type::Uint(256))
+    ///     .mutability(StateMutability::View);
+    ///
+    /// assert_eq!(f.signature(), "balanceOf(address)");
+    /// ```
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            inputs: vec![],
+            outputs: vec![],
+            state_mutability: StateMutability::NonPayable,
+        }
+    }
+
+    /// Appends an input parameter to the function.
+    pub fn input(mut self, name: impl Into<String>, type_: Type) -> Self {
+        self.inputs.push(Param {
+            name: name.into(),
+            type_,
+            indexed: None,
+        });
+        self
+    }
+
+    /// Appends an output parameter to the function.
+    pub fn output(mut self, name: impl Into<String>, type_: Type) -> Self {
+        self.outputs.push(Param {
+            name: name.into(),
+            type_,
+            indexed: None,
+        });
+        self
+    }
 
-        let mut keccak_out = [0u8; 32];
-        let mut hasher = Keccak::v256();
-        hasher.update(self.signature().as_bytes());
-        hasher.finalize(&mut keccak_out);
+    /// Sets the function's state mutability kind.
+    pub fn mutability(mut self, state_mutability: StateMutability) -> Self {
+        self.state_mutability = state_mutability;
+        self
+    }
 
-        let mut mid = [0u8; 4];
-        mid.copy_from_slice(&keccak_out[0..4]);
+    /// Computes the function's method id (function selector).
+    pub fn method_id(&self) -> [u8; 4] {
+        self.method_id_with::<TinyKeccak>()
+    }
 
-        mid
+    /// Computes the function's method id (function selector) using the given
+    /// Keccak-256 implementation.
+    pub fn method_id_with<H: Keccak256>(&self) -> [u8; 4] {
+        keccak::selector::<H>(&self.signature())
     }
 
     /// Returns the function's signature.
@@ -215,31 +857,57 @@ impl Function {
             self.name,
             self.inputs
                 .iter()
-                .map(|param| param.type_.to_string())
+                .map(|param| param.type_.canonical_string())
                 .collect::<Vec<_>>()
                 .join(",")
         )
     }
 
+    /// Returns whether any of the function's inputs has a dynamic type.
+    pub fn has_dynamic_inputs(&self) -> bool {
+        self.inputs.iter().any(Param::is_dynamic)
+    }
+
+    /// Returns whether the function is a getter, i.e. it's read-only and takes no
+    /// inputs, so it can be called without any arguments to probe contract state.
+    pub fn is_getter(&self) -> bool {
+        self.is_read_only() && self.inputs.is_empty()
+    }
+
+    /// Returns whether the function accepts Ether.
+    pub fn is_payable(&self) -> bool {
+        self.state_mutability.is_payable()
+    }
+
+    /// Returns whether the function is guaranteed not to modify the blockchain state.
+    pub fn is_read_only(&self) -> bool {
+        self.state_mutability.is_read_only()
+    }
+
+    /// Returns whether the function may modify the blockchain state.
+    pub fn is_mutating(&self) -> bool {
+        self.state_mutability.is_mutating()
+    }
+
     // Decode function input from slice.
     pub fn decode_input_from_slice(&self, input: &[u8]) -> Result<DecodedParams> {
-        let inputs_types = self
-            .inputs
-            .iter()
-            .map(|f_input| f_input.type_.clone())
-            .collect::<Vec<_>>();
+        DecodedParams::decode(&self.inputs, input)
+    }
 
-        Ok(DecodedParams::from(
-            self.inputs
-                .iter()
-                .cloned()
-                .zip(Value::decode_from_slice(input, &inputs_types)?)
-                .collect::<Vec<_>>(),
-        ))
+    /// Decode function output (return data) from slice.
+    ///
+    /// A function returning a single struct has a single tuple output, which is
+    /// decoded as a [`Value::Tuple`].
+    pub fn decode_output_from_slice(&self, output: &[u8]) -> Result<DecodedParams> {
+        DecodedParams::decode(&self.outputs, output)
     }
 }
 
 /// Available state mutability values for functions and constructors.
+///
+/// State mutability kinds are ordered from the most to the least restrictive:
+/// `Pure < View < NonPayable < Payable`, so e.g. read-only kinds are the ones
+/// `<= StateMutability::View`.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum StateMutability {
@@ -253,6 +921,49 @@ pub enum StateMutability {
     Payable,
 }
 
+impl StateMutability {
+    // Position of the kind in the restrictiveness order, which is kept explicit
+    // rather than tied to the declaration order.
+    fn restrictiveness_rank(&self) -> u8 {
+        match self {
+            StateMutability::Pure => 0,
+            StateMutability::View => 1,
+            StateMutability::NonPayable => 2,
+            StateMutability::Payable => 3,
+        }
+    }
+
+    /// Returns whether the state mutability kind accepts Ether (`payable`).
+    pub fn is_payable(&self) -> bool {
+        matches!(self, StateMutability::Payable)
+    }
+
+    /// Returns whether the state mutability kind does not modify the blockchain state
+    /// (`view` or `pure`).
+    pub fn is_read_only(&self) -> bool {
+        matches!(self, StateMutability::View | StateMutability::Pure)
+    }
+
+    /// Returns whether the state mutability kind may modify the blockchain state
+    /// (`payable` or `nonpayable`).
+    pub fn is_mutating(&self) -> bool {
+        matches!(self, StateMutability::Payable | StateMutability::NonPayable)
+    }
+}
+
+impl PartialOrd for StateMutability {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for StateMutability {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.restrictiveness_rank()
+            .cmp(&other.restrictiveness_rank())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AbiEntry {
@@ -261,21 +972,141 @@ struct AbiEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    inputs: Option<Vec<Param>>,
+    inputs: Option<Vec<ParamEntry>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    outputs: Option<Vec<Param>>,
+    outputs: Option<Vec<ParamEntry>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     state_mutability: Option<StateMutability>,
     #[serde(skip_serializing_if = "Option::is_none")]
     anonymous: Option<bool>,
+    // Legacy mutability flags, superseded by `stateMutability`.
+    #[serde(skip_serializing)]
+    constant: Option<bool>,
+    #[serde(skip_serializing)]
+    payable: Option<bool>,
+    // Precomputed signature some ABI generators include, either as the signature
+    // string or as its hex encoded hash (selector or topic).
+    #[serde(skip_serializing)]
+    signature: Option<String>,
+}
+
+impl AbiEntry {
+    // Returns the entry's state mutability, falling back to the legacy `payable` and
+    // `constant` flags and then to nonpayable for ABIs that omit it.
+    fn state_mutability(&self) -> StateMutability {
+        match (self.state_mutability, self.payable, self.constant) {
+            (Some(state_mutability), _, _) => state_mutability,
+            (None, Some(true), _) => StateMutability::Payable,
+            (None, _, Some(true)) => StateMutability::View,
+            _ => StateMutability::NonPayable,
+        }
+    }
+}
+
+// Matches a definition against a signature, if the query has parentheses, or a
+// bare name otherwise.
+fn matches_name_or_signature(
+    name: &str,
+    signature: impl FnOnce() -> String,
+    name_or_signature: &str,
+) -> bool {
+    if name_or_signature.contains('(') {
+        signature() == name_or_signature
+    } else {
+        name == name_or_signature
+    }
+}
+
+fn param_entries(params: &[Param]) -> Vec<ParamEntry> {
+    params.iter().map(Param::build_param_entry).collect()
+}
+
+// Deserializes an ABI entry along with its parsed input and output params.
+//
+// Params are parsed from within the entry's map so that errors point at the
+// offending entry.
+struct AbiEntrySeed {
+    max_depth: usize,
+    round_int_sizes: bool,
+}
+
+impl<'de> DeserializeSeed<'de> for AbiEntrySeed {
+    type Value = (AbiEntry, Vec<Param>, Vec<Param>, Vec<TypeFixup>);
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for AbiEntrySeed {
+    type Value = (AbiEntry, Vec<Param>, Vec<Param>, Vec<TypeFixup>);
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(formatter, "ABI entry")
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut entry = AbiEntry::deserialize(MapAccessDeserializer::new(map))?;
+
+        let mut fixups = vec![];
+        let inputs = self.params(entry.inputs.take(), &mut fixups)?;
+        let outputs = self.params(entry.outputs.take(), &mut fixups)?;
+
+        let fixups = fixups
+            .into_iter()
+            .map(|(from, to)| TypeFixup {
+                entry_type: entry.type_.clone(),
+                entry_name: entry.name.clone(),
+                from,
+                to,
+            })
+            .collect();
+
+        Ok((entry, inputs, outputs, fixups))
+    }
+}
+
+impl AbiEntrySeed {
+    fn params<E>(
+        &self,
+        entries: Option<Vec<ParamEntry>>,
+        fixups: &mut Vec<(String, String)>,
+    ) -> Result<Vec<Param>, E>
+    where
+        E: serde::de::Error,
+    {
+        entries
+            .unwrap_or_default()
+            .into_iter()
+            .map(|mut entry| {
+                if self.round_int_sizes {
+                    entry.round_int_sizes(fixups);
+                }
+
+                Param::from_entry(entry, self.max_depth).map_err(|err| E::custom(err.to_string()))
+            })
+            .collect()
+    }
 }
 
-struct AbiVisitor;
+// A parsed ABI along with the param type fixups made to accept it and the
+// signatures its entries came with.
+type ParsedAbi = (Abi, Vec<TypeFixup>, Vec<ProvidedSignature>);
+
+struct AbiVisitor {
+    opts: ParseOptions,
+}
 
 impl<'de> Visitor<'de> for AbiVisitor {
-    type Value = Abi;
+    type Value = ParsedAbi;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(formatter, "ABI")
     }
 
@@ -288,29 +1119,40 @@ impl<'de> Visitor<'de> for AbiVisitor {
             functions: vec![],
             events: vec![],
             errors: vec![],
-            has_receive: false,
-            has_fallback: false,
+            receive: None,
+            fallback: None,
         };
 
+        let mut fixups = vec![];
+        let mut signatures = vec![];
+
         loop {
-            let entry = seq.next_element::<AbiEntry>()?;
+            let entry = seq.next_element_seed(AbiEntrySeed {
+                max_depth: self.opts.max_depth,
+                round_int_sizes: !self.opts.strict,
+            })?;
+
+            let entry = entry.map(|(entry, inputs, outputs, entry_fixups)| {
+                fixups.extend(entry_fixups);
+                (entry, inputs, outputs)
+            });
 
             match entry {
-                None => return Ok(abi),
+                None => return Ok((abi, fixups, signatures)),
 
-                Some(entry) => match entry.type_.as_str() {
-                    "receive" => abi.has_receive = true,
+                Some((entry, inputs, outputs)) => match entry.type_.as_str() {
+                    "receive" => {
+                        // receive functions are always payable
+                        abi.receive =
+                            Some(entry.state_mutability.unwrap_or(StateMutability::Payable));
+                    }
 
-                    "fallback" => abi.has_fallback = true,
+                    "fallback" => {
+                        abi.fallback = Some(entry.state_mutability());
+                    }
 
                     "constructor" => {
-                        let state_mutability = entry.state_mutability.ok_or_else(|| {
-                            serde::de::Error::custom(
-                                "missing constructor state mutability".to_string(),
-                            )
-                        })?;
-
-                        let inputs = entry.inputs.unwrap_or_default();
+                        let state_mutability = entry.state_mutability();
 
                         abi.constructor = Some(Constructor {
                             inputs,
@@ -319,49 +1161,61 @@ impl<'de> Visitor<'de> for AbiVisitor {
                     }
 
                     "function" => {
-                        let state_mutability = entry.state_mutability.ok_or_else(|| {
-                            serde::de::Error::custom(
-                                "missing function state mutability".to_string(),
-                            )
-                        })?;
-
-                        let inputs = entry.inputs.unwrap_or_default();
-
-                        let outputs = entry.outputs.unwrap_or_default();
+                        let state_mutability = entry.state_mutability();
 
                         let name = entry.name.ok_or_else(|| {
                             serde::de::Error::custom("missing function name".to_string())
                         })?;
 
-                        abi.functions.push(Function {
+                        let function = Function {
                             name,
                             inputs,
                             outputs,
                             state_mutability,
-                        });
+                        };
+
+                        if let Some(signature) = entry.signature {
+                            signatures.push(self.check_signature(
+                                "function",
+                                &function.name,
+                                signature,
+                                &function.signature(),
+                                &function.method_id(),
+                            )?);
+                        }
+
+                        abi.functions.push(function);
                     }
 
                     "event" => {
-                        let inputs = entry.inputs.unwrap_or_default();
-
                         let name = entry.name.ok_or_else(|| {
-                            serde::de::Error::custom("missing function name".to_string())
+                            serde::de::Error::custom("missing event name".to_string())
                         })?;
 
                         let anonymous = entry.anonymous.ok_or_else(|| {
                             serde::de::Error::custom("missing event anonymous field".to_string())
                         })?;
 
-                        abi.events.push(Event {
+                        let event = Event {
                             name,
                             inputs,
                             anonymous,
-                        });
+                        };
+
+                        if let Some(signature) = entry.signature {
+                            signatures.push(self.check_signature(
+                                "event",
+                                &event.name,
+                                signature,
+                                &event.signature(),
+                                event.topic().as_bytes(),
+                            )?);
+                        }
+
+                        abi.events.push(event);
                     }
 
                     "error" => {
-                        let inputs = entry.inputs.unwrap_or_default();
-
                         let name = entry.name.ok_or_else(|| {
                             serde::de::Error::custom("missing error name".to_string())
                         })?;
@@ -370,10 +1224,12 @@ impl<'de> Visitor<'de> for AbiVisitor {
                     }
 
                     _ => {
-                        return Err(serde::de::Error::custom(format!(
-                            "invalid ABI entry type: {}",
-                            entry.type_
-                        )))
+                        if self.opts.strict {
+                            return Err(serde::de::Error::custom(format!(
+                                "invalid ABI entry type: {}",
+                                entry.type_
+                            )));
+                        }
                     }
                 },
             }
@@ -381,35 +1237,83 @@ impl<'de> Visitor<'de> for AbiVisitor {
     }
 }
 
+impl AbiVisitor {
+    // Checks an ABI entry's precomputed signature against the given computed
+    // signature and its hash, rejecting mismatches in strict mode.
+    fn check_signature<E>(
+        &self,
+        entry_type: &str,
+        entry_name: &str,
+        provided: String,
+        signature: &str,
+        hash: &[u8],
+    ) -> Result<ProvidedSignature, E>
+    where
+        E: serde::de::Error,
+    {
+        let matches = match provided.strip_prefix("0x") {
+            Some(hex_hash) => hex_hash.eq_ignore_ascii_case(&hex::encode(hash)),
+            None => provided == signature,
+        };
+
+        if self.opts.strict && !matches {
+            return Err(E::custom(format!(
+                "signature mismatch: computed {}, ABI entry has {}",
+                signature, provided
+            )));
+        }
+
+        Ok(ProvidedSignature {
+            entry_type: entry_type.to_string(),
+            entry_name: entry_name.to_string(),
+            signature: provided,
+            matches,
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use std::str::FromStr;
+
     use ethereum_types::{H160, U256};
     use pretty_assertions::assert_eq;
 
-    use crate::types::Type;
-
     use super::*;
 
-    const TEST_ABI_V1: &str = r#"[{"inputs":[{"internalType":"address","name":"a","type":"address"}],"stateMutability":"nonpayable","type":"constructor"},{"anonymous":false,"inputs":[{"indexed":false,"internalType":"address","name":"x","type":"address"},{"indexed":false,"internalType":"uint256","name":"y","type":"uint256"}],"name":"E","type":"event"},{"inputs":[{"internalType":"uint256","name":"x","type":"uint256"}],"name":"f","outputs":[{"internalType":"uint256","name":"","type":"uint256"}],"stateMutability":"nonpayable","type":"function"},{"stateMutability":"payable","type":"receive"},{"type":"error","inputs": [{"name":"x","type":"uint256"},{"name":"y","type":"uint256"}],"name":"Err"}]"#;
+    const TEST_ABI_V1: &str = r#"[{"inputs":[{"internalType":"address","name":"a","type":"address"}],"stateMutability":"nonpayable","type":"constructor"},{"anonymous":false,"inputs":[{"indexed":false,"internalType":"address","name":"x","type":"address"},{"indexed":false,"internalType":"uint256","name":"y","type":"uint256"}],"name":"E","type":"event"},{"inputs":[{"internalType":"uint256","name":"x","type":"uint256"}],"name":"f","outputs":[{"internalType":"uint256","name":"","type":"uint256"}],"stateMutability":"nonpayable","type":"function"},{"stateMutability":"payable","type":"receive"},{"stateMutability":"nonpayable","type":"fallback"},{"type":"error","inputs": [{"name":"x","type":"uint256"},{"name":"y","type":"uint256"}],"name":"Err"}]"#;
 
     fn test_function() -> Function {
-        Function {
-            name: "funname".to_string(),
-            inputs: vec![
-                Param {
-                    name: "".to_string(),
-                    type_: Type::Address,
-                    indexed: None,
-                },
-                Param {
+        Function::new("funname")
+            .input("", Type::Address)
+            .input("x", Type::FixedArray(Box::new(Type::Uint(56)), 2))
+            .mutability(StateMutability::Pure)
+    }
+
+    #[test]
+    fn function_builder() {
+        let fun = Function::new("f")
+            .input("x", Type::Uint(256))
+            .output("", Type::Bool)
+            .mutability(StateMutability::View);
+
+        assert_eq!(
+            fun,
+            Function {
+                name: "f".to_string(),
+                inputs: vec![Param {
                     name: "x".to_string(),
-                    type_: Type::FixedArray(Box::new(Type::Uint(56)), 2),
+                    type_: Type::Uint(256),
                     indexed: None,
-                },
-            ],
-            outputs: vec![],
-            state_mutability: StateMutability::Pure,
-        }
+                }],
+                outputs: vec![Param {
+                    name: "".to_string(),
+                    type_: Type::Bool,
+                    indexed: None,
+                }],
+                state_mutability: StateMutability::View,
+            }
+        );
     }
 
     #[test]
@@ -424,6 +1328,189 @@ mod test {
         assert_eq!(fun.method_id(), [0x83, 0x1f, 0xc7, 0x20]);
     }
 
+    #[test]
+    fn function_method_id_with_tuples() {
+        fn tuple(tys: Vec<Type>) -> Type {
+            Type::Tuple(tys.into_iter().map(|ty| (String::new(), ty)).collect())
+        }
+
+        // Uniswap V3 SwapRouter
+        let exact_input_single = Function::new("exactInputSingle").input(
+            "params",
+            tuple(vec![
+                Type::Address,
+                Type::Address,
+                Type::Uint(24),
+                Type::Address,
+                Type::Uint(256),
+                Type::Uint(256),
+                Type::Uint(256),
+                Type::Uint(160),
+            ]),
+        );
+        assert_eq!(
+            exact_input_single.signature(),
+            "exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))"
+        );
+        assert_eq!(exact_input_single.method_id(), [0x41, 0x4b, 0xf3, 0x89]);
+
+        let exact_input = Function::new("exactInput").input(
+            "params",
+            tuple(vec![
+                Type::Bytes,
+                Type::Address,
+                Type::Uint(256),
+                Type::Uint(256),
+                Type::Uint(256),
+            ]),
+        );
+        assert_eq!(exact_input.method_id(), [0xc0, 0x4b, 0x8d, 0x59]);
+
+        // Multicall3
+        let aggregate = Function::new("aggregate").input(
+            "calls",
+            Type::Array(Box::new(tuple(vec![Type::Address, Type::Bytes]))),
+        );
+        assert_eq!(aggregate.signature(), "aggregate((address,bytes)[])");
+        assert_eq!(aggregate.method_id(), [0x25, 0x2d, 0xba, 0x42]);
+
+        let aggregate3 = Function::new("aggregate3").input(
+            "calls",
+            Type::Array(Box::new(tuple(vec![
+                Type::Address,
+                Type::Bool,
+                Type::Bytes,
+            ]))),
+        );
+        assert_eq!(aggregate3.method_id(), [0x82, 0xad, 0x56, 0xcb]);
+    }
+
+    #[test]
+    fn function_has_dynamic_inputs() {
+        let f = Function::new("transfer")
+            .input("to", Type::Address)
+            .input("amount", Type::Uint(256));
+        assert!(!f.has_dynamic_inputs());
+
+        let f = f.input("data", Type::Bytes);
+        assert!(f.has_dynamic_inputs());
+
+        assert!(!Function::new("f").has_dynamic_inputs());
+    }
+
+    #[test]
+    fn read_and_write_functions() {
+        let abi = Abi::from_human_readable(&[
+            "function owner() view returns (address)",
+            "function balanceOf(address account) view returns (uint256)",
+            "function hash(bytes data) pure returns (bytes32)",
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function deposit() payable",
+        ])
+        .expect("from_human_readable failed");
+
+        let names = |fs: Vec<&Function>| fs.iter().map(|f| f.name.clone()).collect::<Vec<_>>();
+
+        assert_eq!(
+            names(abi.read_functions()),
+            vec!["owner", "balanceOf", "hash"]
+        );
+        assert_eq!(names(abi.write_functions()), vec!["transfer", "deposit"]);
+
+        let getters: Vec<_> = abi.functions.iter().filter(|f| f.is_getter()).collect();
+        assert_eq!(names(getters), vec!["owner"]);
+    }
+
+    #[test]
+    fn state_mutability_ordering() {
+        use StateMutability::*;
+
+        let mut kinds = vec![Payable, View, NonPayable, Pure];
+        kinds.sort();
+        assert_eq!(kinds, vec![Pure, View, NonPayable, Payable]);
+
+        for kind in kinds {
+            assert_eq!(kind <= View, kind.is_read_only());
+            assert_eq!(kind >= NonPayable, kind.is_mutating());
+        }
+    }
+
+    #[test]
+    fn state_mutability_predicates() {
+        assert!(StateMutability::Payable.is_payable());
+        assert!(!StateMutability::NonPayable.is_payable());
+
+        assert!(StateMutability::Pure.is_read_only());
+        assert!(StateMutability::View.is_read_only());
+        assert!(!StateMutability::NonPayable.is_read_only());
+        assert!(!StateMutability::Payable.is_read_only());
+
+        assert!(StateMutability::NonPayable.is_mutating());
+        assert!(StateMutability::Payable.is_mutating());
+        assert!(!StateMutability::View.is_mutating());
+        assert!(!StateMutability::Pure.is_mutating());
+
+        let fun = test_function();
+        assert!(fun.is_read_only());
+        assert!(!fun.is_mutating());
+        assert!(!fun.is_payable());
+    }
+
+    #[test]
+    fn function_decode_output_from_slice() {
+        let record = Type::Tuple(vec![
+            ("id".to_string(), Type::Uint(256)),
+            ("owner".to_string(), Type::Address),
+            ("active".to_string(), Type::Bool),
+        ]);
+        let fun = Function::new("record")
+            .input("id", Type::Uint(256))
+            .output("", record)
+            .mutability(StateMutability::View);
+
+        let owner = H160::from_low_u64_be(0xabcd);
+        let record_value = Value::Tuple(vec![
+            ("id".to_string(), Value::Uint(U256::from(7), 256)),
+            ("owner".to_string(), Value::Address(owner)),
+            ("active".to_string(), Value::Bool(true)),
+        ]);
+
+        // static tuples are encoded in place
+        let output = Value::encode(std::slice::from_ref(&record_value));
+        assert_eq!(output.len(), 96);
+
+        let decoded = fun
+            .decode_output_from_slice(&output)
+            .expect("decode_output_from_slice failed");
+        assert_eq!(
+            decoded,
+            DecodedParams::from(vec![(fun.outputs[0].clone(), record_value)])
+        );
+
+        // dynamic tuples are encoded after their offset
+        let named = Type::Tuple(vec![
+            ("id".to_string(), Type::Uint(256)),
+            ("name".to_string(), Type::String),
+        ]);
+        let fun = Function::new("named")
+            .output("", named)
+            .output("count", Type::Uint(8));
+
+        let named_value = Value::Tuple(vec![
+            ("id".to_string(), Value::Uint(U256::from(1), 256)),
+            ("name".to_string(), Value::String("abc".to_string())),
+        ]);
+        let values = vec![named_value, Value::Uint(U256::from(2), 8)];
+        let output = Value::encode(&values);
+        assert_eq!(output[31], 0x40);
+
+        let decoded = fun
+            .decode_output_from_slice(&output)
+            .expect("decode_output_from_slice failed");
+        assert_eq!(decoded.values().cloned().collect::<Vec<_>>(), values);
+        assert_eq!(decoded.get("count"), Some(&Value::Uint(U256::from(2), 8)));
+    }
+
     #[test]
     fn abi_function_decode_input_from_slice() {
         let addr = H160::random();
@@ -444,8 +1531,8 @@ mod test {
             functions: vec![fun],
             events: vec![],
             errors: vec![],
-            has_receive: false,
-            has_fallback: false,
+            receive: None,
+            fallback: None,
         };
 
         let mut enc_input = abi.functions[0].method_id().to_vec();
@@ -467,6 +1554,524 @@ mod test {
         assert_eq!(dec, (&abi.functions[0], expected_decoded_params));
     }
 
+    #[test]
+    fn abi_function_decode_input_without_args() {
+        let fun = Function::new("f");
+        let abi = Abi {
+            constructor: None,
+            functions: vec![fun],
+            events: vec![],
+            errors: vec![],
+            receive: None,
+            fallback: None,
+        };
+
+        let enc_input = abi.functions[0].method_id().to_vec();
+
+        let dec = abi
+            .decode_input_from_slice(&enc_input)
+            .expect("decode_input_from_slice failed");
+
+        assert_eq!(dec, (&abi.functions[0], DecodedParams::from(vec![])));
+
+        assert!(abi.decode_input_from_slice(&enc_input[..3]).is_err());
+        assert!(abi.decode_input_from_slice(&[]).is_err());
+    }
+
+    #[test]
+    fn abi_split_input() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+
+        let input = hex::decode("a1671295000000000000000000000000a0b211418d87c9f5918e6213fec3b13290aa5f26000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20000000000000000000000000000000000000000000000000000000000000bb8").unwrap();
+
+        let (f, args) = abi.split_input(&input).expect("split_input failed");
+        assert_eq!(f.name, "createPool");
+        assert_eq!(args, &input[4..]);
+        assert_eq!(args.len(), 3 * 32);
+        assert_eq!(args.as_ptr(), input[4..].as_ptr());
+
+        let (_, args) = abi.split_input(&input[..4]).expect("split_input failed");
+        assert!(args.is_empty());
+
+        assert!(abi.split_input(&input[..3]).is_err());
+        assert!(abi.split_input(&[0; 4]).is_err());
+    }
+
+    #[test]
+    fn abi_decode_multicall() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+
+        let owner_call = abi
+            .functions
+            .iter()
+            .find(|f| f.name == "owner")
+            .unwrap()
+            .method_id();
+        let set_owner = abi.functions.iter().find(|f| f.name == "setOwner").unwrap();
+
+        let mut set_owner_call = set_owner.method_id().to_vec();
+        set_owner_call.extend(Value::encode(&[Value::Address(H160::repeat_byte(1))]));
+
+        let calls = vec![
+            owner_call.to_vec(),
+            vec![0x12, 0x34, 0x56, 0x78],
+            set_owner_call,
+            vec![],
+        ];
+
+        let results = abi.decode_multicall(&calls);
+        assert_eq!(results.len(), 4);
+
+        assert_eq!(results[0].as_ref().unwrap().0.name, "owner");
+        assert!(results[1].is_err());
+        assert_eq!(
+            results[2].as_ref().unwrap().1.get("_owner"),
+            Some(&Value::Address(H160::repeat_byte(1)))
+        );
+        assert!(results[3].is_err());
+    }
+
+    #[test]
+    fn event_by_topic() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+
+        let topic =
+            H256::from_str("783cca1c0412dd0d695e784568c96da2e9c22ff989357a2e8b1d9b2b4e6b7118")
+                .unwrap();
+
+        let evt = abi.event_by_topic(topic).expect("event not found");
+        assert_eq!(evt.name, "PoolCreated");
+
+        assert_eq!(abi.event_by_topic(H256::zero()), None);
+    }
+
+    #[test]
+    fn function_by_selector() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+
+        let f = abi
+            .function_by_selector([0xa1, 0x67, 0x12, 0x95])
+            .expect("function not found");
+        assert_eq!(f.signature(), "createPool(address,address,uint24)");
+
+        assert_eq!(abi.function_by_selector([0; 4]), None);
+    }
+
+    #[test]
+    fn event_filter() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+
+        let token1 = H160::from_str("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2").unwrap();
+
+        let filter = abi
+            .event_filter(
+                "PoolCreated",
+                &[
+                    None,
+                    Some(Value::Address(token1)),
+                    Some(Value::Uint(U256::from(3000), 24)),
+                ],
+            )
+            .expect("event_filter failed");
+
+        assert_eq!(
+            filter,
+            serde_json::json!([
+                "0x783cca1c0412dd0d695e784568c96da2e9c22ff989357a2e8b1d9b2b4e6b7118",
+                null,
+                "0x000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                "0x0000000000000000000000000000000000000000000000000000000000000bb8",
+            ])
+        );
+
+        assert!(abi.event_filter("Missing", &[]).is_err());
+        assert!(abi
+            .event_filter("PoolCreated", &[Some(Value::Bool(true))])
+            .is_err());
+
+        let abi = Abi {
+            constructor: None,
+            functions: vec![],
+            events: vec![
+                Event::new("E").input("a", Type::Bool, true),
+                Event::new("E")
+                    .input("a", Type::Bool, true)
+                    .input("b", Type::Bool, true),
+            ],
+            errors: vec![],
+            receive: None,
+            fallback: None,
+        };
+
+        let filter = abi
+            .event_filter("E", &[None, Some(Value::Bool(true))])
+            .expect("event_filter failed");
+        assert_eq!(filter.as_array().map(Vec::len), Some(3));
+        assert!(abi.event_filter("E", &[]).is_err());
+    }
+
+    #[test]
+    fn signatures() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+
+        assert_eq!(
+            abi.function_signatures(),
+            vec![
+                "createPool(address,address,uint24)",
+                "enableFeeAmount(uint24,int24)",
+                "feeAmountTickSpacing(uint24)",
+                "getPool(address,address,uint24)",
+                "owner()",
+                "parameters()",
+                "setOwner(address)",
+            ]
+        );
+        assert_eq!(
+            abi.event_signatures(),
+            vec![
+                "FeeAmountEnabled(uint24,int24)",
+                "OwnerChanged(address,address)",
+                "PoolCreated(address,address,uint24,int24,address)",
+            ]
+        );
+        assert!(abi.error_signatures().is_empty());
+
+        let abi: Abi = serde_json::from_str(TEST_ABI_V1).unwrap();
+        assert_eq!(abi.error_signatures(), vec!["Err(uint256,uint256)"]);
+    }
+
+    #[test]
+    fn has_function_event_and_error() {
+        let abi = Abi {
+            constructor: None,
+            functions: vec![
+                Function::new("transfer")
+                    .input("to", Type::Address)
+                    .input("amount", Type::Uint(256)),
+                Function::new("transfer").input("to", Type::Address),
+            ],
+            events: vec![Event::new("Transfer").input("from", Type::Address, true)],
+            errors: vec![Error {
+                name: "Unauthorized".to_string(),
+                inputs: vec![],
+            }],
+            receive: None,
+            fallback: None,
+        };
+
+        assert!(abi.has_function("transfer"));
+        assert!(abi.has_function("transfer(address,uint256)"));
+        assert!(abi.has_function("transfer(address)"));
+        assert!(!abi.has_function("transfer(uint256)"));
+        assert!(!abi.has_function("approve"));
+        assert!(!abi.has_function("Transfer"));
+
+        assert!(abi.has_event("Transfer"));
+        assert!(abi.has_event("Transfer(address)"));
+        assert!(!abi.has_event("Transfer(address,address)"));
+        assert!(!abi.has_event("transfer"));
+
+        assert!(abi.has_error("Unauthorized"));
+        assert!(abi.has_error("Unauthorized()"));
+        assert!(!abi.has_error("Unauthorized(address)"));
+        assert!(!abi.has_error("Transfer"));
+    }
+
+    #[test]
+    fn selectors_and_topics() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+
+        let selectors: std::collections::HashMap<_, _> = abi.selectors().collect();
+        assert_eq!(selectors.len(), abi.functions.len());
+        assert_eq!(
+            selectors[&[0xa1, 0x67, 0x12, 0x95]].signature(),
+            "createPool(address,address,uint24)"
+        );
+
+        let topics: std::collections::HashMap<_, _> = abi.topics().collect();
+        assert_eq!(topics.len(), abi.events.len());
+        for (topic, evt) in &topics {
+            assert_eq!(abi.event_by_topic(*topic), Some(*evt));
+        }
+
+        let abi = Abi {
+            constructor: None,
+            functions: vec![],
+            events: vec![
+                Event::new("A").anonymous(true),
+                Event::new("B").input("x", Type::Uint(256), true),
+            ],
+            errors: vec![],
+            receive: None,
+            fallback: None,
+        };
+
+        let topics: Vec<_> = abi.topics().map(|(_, e)| e.name.as_str()).collect();
+        assert_eq!(topics, vec!["B"]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn selector_map() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+
+        let selector_map = abi.selector_map();
+        assert_eq!(selector_map.len(), abi.functions.len());
+        assert_eq!(
+            selector_map[&[0xa1, 0x67, 0x12, 0x95]],
+            "createPool(address,address,uint24)"
+        );
+        assert_eq!(selector_map.get(&[0, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn decode_input_to_json() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+        let input = "0xa1671295000000000000000000000000a0b211418d87c9f5918e6213fec3b13290aa5f26000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20000000000000000000000000000000000000000000000000000000000000bb8";
+
+        let decoded = abi
+            .decode_input_to_json(input)
+            .expect("decode_input_to_json failed");
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&decoded).unwrap(),
+            serde_json::json!({
+                "function": "createPool",
+                "signature": "createPool(address,address,uint24)",
+                "args": [
+                    {
+                        "name": "tokenA",
+                        "type": "address",
+                        "value": "0xa0b211418d87c9f5918e6213fec3b13290aa5f26",
+                    },
+                    {
+                        "name": "tokenB",
+                        "type": "address",
+                        "value": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                    },
+                    {
+                        "name": "fee",
+                        "type": "uint24",
+                        "value": "3000",
+                    },
+                ],
+            })
+        );
+
+        let err = abi
+            .decode_input_to_json("0x12345678")
+            .expect_err("decode_input_to_json succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::UnknownSelector {
+                selector: [0x12, 0x34, 0x56, 0x78]
+            })
+        );
+    }
+
+    #[test]
+    fn summary() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+
+        assert_eq!(abi.function_count(), 7);
+        assert_eq!(abi.event_count(), 3);
+        assert_eq!(abi.error_count(), 0);
+
+        let summary = abi.summary();
+        assert_eq!(
+            summary,
+            AbiSummary {
+                pure_functions: 0,
+                view_functions: 4,
+                nonpayable_functions: 3,
+                payable_functions: 0,
+                events: 3,
+                errors: 0,
+                has_constructor: true,
+                has_receive: false,
+                has_fallback: false,
+            }
+        );
+        assert_eq!(summary.functions(), abi.function_count());
+
+        let abi: Abi = serde_json::from_str(TEST_ABI_V1).unwrap();
+        let summary = abi.summary();
+        assert_eq!(summary.errors, 1);
+        assert!(summary.has_receive);
+        assert!(summary.has_fallback);
+    }
+
+    #[test]
+    fn abi_index() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+        let index = abi.index();
+
+        for (selector, f) in abi.selectors() {
+            assert_eq!(index.function_by_selector(selector), Some(f));
+        }
+        assert_eq!(index.function_by_selector([0; 4]), None);
+
+        for (topic, e) in abi.topics() {
+            assert_eq!(index.event_by_topic(topic), Some(e));
+        }
+        assert_eq!(index.event_by_topic(H256::zero()), None);
+
+        let input = hex::decode("a1671295000000000000000000000000a0b211418d87c9f5918e6213fec3b13290aa5f26000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20000000000000000000000000000000000000000000000000000000000000bb8").unwrap();
+        assert_eq!(
+            index
+                .decode_input_from_slice(&input)
+                .expect("decode_input_from_slice failed"),
+            abi.decode_input_from_slice(&input)
+                .expect("decode_input_from_slice failed")
+        );
+        assert!(index.decode_input_from_slice(&input[..3]).is_err());
+
+        let err = index
+            .decode_input_from_slice(&[1, 2, 3, 4])
+            .expect_err("decode_input_from_slice succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::UnknownSelector {
+                selector: [1, 2, 3, 4]
+            })
+        );
+
+        let topics = vec![
+            H256::from_str("783cca1c0412dd0d695e784568c96da2e9c22ff989357a2e8b1d9b2b4e6b7118")
+                .unwrap(),
+            H256::from_low_u64_be(1),
+            H256::from_low_u64_be(2),
+            H256::from_low_u64_be(3000),
+        ];
+        let data = hex::decode("000000000000000000000000000000000000000000000000000000000000003c0000000000000000000000001d42064fc4beb5f8aaf85f4617ae8b3b5b8bd801").unwrap();
+        assert_eq!(
+            index
+                .decode_log_from_slice(&topics, &data)
+                .expect("decode_log_from_slice failed"),
+            abi.decode_log_from_slice(&topics, &data)
+                .expect("decode_log_from_slice failed")
+        );
+        assert!(index.decode_log_from_slice(&[], &data).is_err());
+    }
+
+    #[test]
+    fn abi_decode_log_from_hex_data() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+
+        // Same log as in examples/log_data.rs.
+        let topics = vec![
+            H256::from_str("783cca1c0412dd0d695e784568c96da2e9c22ff989357a2e8b1d9b2b4e6b7118")
+                .unwrap(),
+            H256::from_str("000000000000000000000000a0b211418d87c9f5918e6213fec3b13290aa5f26")
+                .unwrap(),
+            H256::from_str("000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")
+                .unwrap(),
+            H256::from_low_u64_be(3000),
+        ];
+        let data = crate::hex_to_bytes("0x000000000000000000000000000000000000000000000000000000000000003c000000000000000000000000acabbea9c2d0ff835418d139d6a570b5025be085").unwrap();
+        assert_eq!(data.len(), 64);
+
+        let (evt, decoded) = abi
+            .decode_log_from_slice(&topics, &data)
+            .expect("decode_log_from_slice failed");
+        assert_eq!(evt.name, "PoolCreated");
+        assert_eq!(
+            decoded.get("tickSpacing"),
+            Some(&Value::Int(U256::from(60), 24))
+        );
+        assert_eq!(
+            decoded.get("pool"),
+            Some(&Value::Address(
+                H160::from_str("acabbea9c2d0ff835418d139d6a570b5025be085").unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn abi_function_decode_input_from_hex() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+
+        let input = "a1671295000000000000000000000000a0b211418d87c9f5918e6213fec3b13290aa5f26000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20000000000000000000000000000000000000000000000000000000000000bb8";
+
+        let (f, unprefixed) = abi
+            .decode_input_from_hex(input)
+            .expect("decode_input_from_hex failed");
+        assert_eq!(f.name, "createPool");
+
+        let (_, prefixed) = abi
+            .decode_input_from_hex(&format!("0x{}", input))
+            .expect("decode_input_from_hex failed");
+        assert_eq!(prefixed, unprefixed);
+
+        let (_, padded) = abi
+            .decode_input_from_hex(&format!(" 0x{}\n", input))
+            .expect("decode_input_from_hex failed");
+        assert_eq!(padded, unprefixed);
+
+        let err = abi
+            .decode_input_from_hex(&input[1..])
+            .expect_err("decode_input_from_hex succeeded");
+        assert!(err.to_string().contains("odd number of digits"));
+    }
+
+    #[test]
+    fn abi_decode_transaction_input() {
+        let abi: Abi = serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json"))
+            .expect("failed to parse ABI");
+
+        let input = "0xa1671295000000000000000000000000a0b211418d87c9f5918e6213fec3b13290aa5f26000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20000000000000000000000000000000000000000000000000000000000000bb8";
+
+        let call = abi
+            .decode_transaction_input(input)
+            .expect("decode_transaction_input failed")
+            .expect("missing decoded call");
+
+        assert_eq!(call.function.name, "createPool");
+        assert_eq!(call.selector, [0xa1, 0x67, 0x12, 0x95]);
+        assert_eq!(call.params, abi.decode_input_from_hex(input).unwrap().1);
+
+        assert_eq!(abi.decode_transaction_input("0x").unwrap(), None);
+        assert_eq!(abi.decode_transaction_input("").unwrap(), None);
+
+        let err = abi
+            .decode_transaction_input("0xdeadbeef0000")
+            .expect_err("decode_transaction_input succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::UnknownSelector {
+                selector: [0xde, 0xad, 0xbe, 0xef]
+            })
+        );
+        assert_eq!(
+            err.to_string(),
+            "ABI function not found for selector 0xdeadbeef"
+        );
+
+        let err = abi
+            .index()
+            .decode_input_from_slice(&[0xde, 0xad, 0xbe, 0xef])
+            .expect_err("decode_input_from_slice succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::UnknownSelector {
+                selector: [0xde, 0xad, 0xbe, 0xef]
+            })
+        );
+    }
+
     #[test]
     fn works_v1() {
         let abi: Abi = serde_json::from_str(TEST_ABI_V1).unwrap();
@@ -494,7 +2099,7 @@ mod test {
                         type_: Type::Uint(256),
                         indexed: None
                     }],
-                    state_mutability: StateMutability::NonPayable
+                    state_mutability: StateMutability::NonPayable,
                 }],
                 events: vec![Event {
                     name: "E".to_string(),
@@ -510,7 +2115,7 @@ mod test {
                             indexed: Some(false)
                         }
                     ],
-                    anonymous: false
+                    anonymous: false,
                 }],
                 errors: vec![Error {
                     name: "Err".to_string(),
@@ -527,8 +2132,8 @@ mod test {
                         },
                     ]
                 }],
-                has_receive: true,
-                has_fallback: false
+                receive: Some(StateMutability::Payable),
+                fallback: Some(StateMutability::NonPayable)
             }
         )
     }
@@ -596,12 +2201,376 @@ mod test {
                 }],
                 events: vec![],
                 errors: vec![],
-                has_receive: false,
-                has_fallback: false,
+                receive: None,
+                fallback: None,
             }
         );
     }
 
+    #[test]
+    fn receive_and_fallback_state_mutability() {
+        let abi: Abi = serde_json::from_str(
+            r#"[{"type":"receive","stateMutability":"payable"},{"type":"fallback","stateMutability":"payable"}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(abi.receive, Some(StateMutability::Payable));
+        assert_eq!(abi.fallback, Some(StateMutability::Payable));
+
+        let abi: Abi = serde_json::from_str(r#"[{"type":"fallback"}]"#).unwrap();
+
+        assert_eq!(abi.receive, None);
+        assert_eq!(abi.fallback, Some(StateMutability::NonPayable));
+
+        let ser_abi = serde_json::to_string(&abi).expect("serialized abi");
+        let de_abi: Abi = serde_json::from_str(&ser_abi).expect("deserialized abi");
+
+        assert_eq!(de_abi.fallback, Some(StateMutability::NonPayable));
+
+        // legacy ABIs flag payable fallbacks without a stateMutability
+        let abi: Abi = serde_json::from_str(r#"[{"type":"fallback","payable":true}]"#).unwrap();
+
+        assert_eq!(abi.fallback, Some(StateMutability::Payable));
+    }
+
+    #[test]
+    fn missing_state_mutability() {
+        let abi: Abi = serde_json::from_str(
+            r#"[
+                {"type":"constructor","inputs":[{"name":"a","type":"address"}]},
+                {"type":"function","name":"f","inputs":[],"outputs":[]},
+                {"type":"function","name":"g","inputs":[],"outputs":[],"constant":true},
+                {"type":"function","name":"h","inputs":[],"outputs":[],"payable":true}
+            ]"#,
+        )
+        .expect("ABI deserialized");
+
+        assert_eq!(
+            abi.constructor.map(|c| c.state_mutability),
+            Some(StateMutability::NonPayable)
+        );
+        assert_eq!(
+            abi.functions
+                .iter()
+                .map(|f| f.state_mutability)
+                .collect::<Vec<_>>(),
+            vec![
+                StateMutability::NonPayable,
+                StateMutability::View,
+                StateMutability::Payable
+            ]
+        );
+    }
+
+    #[test]
+    fn legacy_function_entries() {
+        let abi: Abi = serde_json::from_str(
+            r#"[
+                {"type":"function","name":"f","inputs":[{"name":"x","type":"uint256"}]},
+                {"type":"function","name":"g","inputs":[],"constant":false},
+                {"type":"function","name":"h","inputs":[],"outputs":[{"name":"","type":"bool"}],"constant":true}
+            ]"#,
+        )
+        .expect("ABI deserialized");
+
+        assert_eq!(
+            abi.functions,
+            vec![
+                Function::new("f").input("x", Type::Uint(256)),
+                Function::new("g"),
+                Function::new("h")
+                    .output("", Type::Bool)
+                    .mutability(StateMutability::View),
+            ]
+        );
+
+        // an explicit stateMutability wins over the legacy flags, and payable over
+        // constant
+        let abi: Abi = serde_json::from_str(
+            r#"[
+                {"type":"constructor","inputs":[],"payable":true},
+                {"type":"function","name":"f","inputs":[],"constant":true,"stateMutability":"pure"},
+                {"type":"function","name":"g","inputs":[],"constant":true,"payable":true},
+                {"type":"function","name":"h","inputs":[],"constant":false,"payable":false}
+            ]"#,
+        )
+        .expect("ABI deserialized");
+
+        assert_eq!(
+            abi.constructor.map(|c| c.state_mutability),
+            Some(StateMutability::Payable)
+        );
+        assert_eq!(
+            abi.functions
+                .iter()
+                .map(|f| f.state_mutability)
+                .collect::<Vec<_>>(),
+            vec![
+                StateMutability::Pure,
+                StateMutability::Payable,
+                StateMutability::NonPayable
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_entry() {
+        // the middle entry has an invalid param type
+        let abi_json = r#"[
+            {"type":"function","name":"f","inputs":[],"stateMutability":"view"},
+            {"type":"function","name":"g","inputs":[{"name":"x","type":"uint257"}],"stateMutability":"view"},
+            {"type":"function","name":"h","inputs":[],"stateMutability":"view"}
+        ]"#;
+
+        assert!(serde_json::from_str::<Abi>(abi_json).is_err());
+
+        // the middle entry is not an object
+        let abi_json = r#"[
+            {"type":"function","name":"f","inputs":[],"stateMutability":"view"},
+            42,
+            {"type":"function","name":"h","inputs":[],"stateMutability":"view"}
+        ]"#;
+
+        assert!(serde_json::from_str::<Abi>(abi_json).is_err());
+
+        let err =
+            serde_json::from_str::<Abi>(r#"[{"type":"event","inputs":[],"anonymous":false}]"#)
+                .expect_err("ABI deserialized");
+        assert!(err.to_string().contains("missing event name"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_file() {
+        let abi = Abi::from_file("examples/uniswapv3factory_abi.json").expect("from_file failed");
+        assert_eq!(
+            abi,
+            serde_json::from_str(include_str!("../examples/uniswapv3factory_abi.json")).unwrap()
+        );
+
+        let err = Abi::from_file("examples/missing_abi.json").expect_err("from_file succeeded");
+        assert!(err.downcast_ref::<std::io::Error>().is_some());
+        assert!(err.to_string().contains("examples/missing_abi.json"));
+
+        let path = std::env::temp_dir().join("ethereum_abi_from_file_invalid.json");
+        std::fs::write(&path, "[{]").unwrap();
+        let err = Abi::from_file(&path).expect_err("from_file succeeded");
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            err.downcast_ref::<AbiError>(),
+            Some(AbiError::Json { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn from_artifact_str() {
+        let artifact = format!(
+            r#"{{
+                "_format": "hh-sol-artifact-1",
+                "contractName": "C",
+                "sourceName": "contracts/C.sol",
+                "abi": {},
+                "bytecode": "0x",
+                "deployedBytecode": "0x",
+                "linkReferences": {{}},
+                "deployedLinkReferences": {{}}
+            }}"#,
+            TEST_ABI_V1
+        );
+
+        assert_eq!(
+            Abi::from_artifact_str(&artifact).expect("from_artifact_str failed"),
+            serde_json::from_str::<Abi>(TEST_ABI_V1).unwrap()
+        );
+
+        assert!(Abi::from_artifact_str(r#"{"contractName": "C"}"#).is_err());
+        assert!(Abi::from_artifact_str(TEST_ABI_V1).is_err());
+    }
+
+    #[test]
+    fn malformed_entry_position() {
+        let abi_json = r#"[
+            {"type":"function","name":"f","inputs":[],"stateMutability":"view"},
+            {"type":"function","name":"g","inputs":[{"name":"x","type":"uint257"}],"stateMutability":"view"},
+            {"type":"function","name":"h","inputs":[],"stateMutability":"view"}
+        ]"#;
+
+        let err = Abi::from_str_with(abi_json, &ParseOptions::default())
+            .expect_err("from_str_with succeeded");
+
+        match err.downcast_ref::<AbiError>() {
+            Some(AbiError::Json { line, message, .. }) => {
+                assert_eq!(*line, 3);
+                assert!(message.contains("uint257"));
+            }
+            _ => panic!("unexpected error: {}", err),
+        }
+    }
+
+    #[test]
+    fn parse_round_int_sizes() {
+        let abi_json = r#"[
+            {"type":"function","name":"f","inputs":[{"name":"x","type":"uint7"},{"name":"y","type":"int12[]"}],"outputs":[{"name":"","type":"uint256"}],"stateMutability":"view"},
+            {"type":"constructor","inputs":[{"name":"x","type":"uint255"}],"stateMutability":"nonpayable"},
+            {"type":"event","name":"E","inputs":[{"name":"x","type":"uint8","indexed":true}],"anonymous":false}
+        ]"#;
+
+        let (abi, fixups) = Abi::from_str_with_fixups(abi_json, &ParseOptions::default())
+            .expect("from_str_with_fixups failed");
+
+        assert_eq!(abi.functions[0].signature(), "f(uint8,int16[])");
+        assert_eq!(
+            fixups,
+            vec![
+                TypeFixup {
+                    entry_type: "function".to_string(),
+                    entry_name: Some("f".to_string()),
+                    from: "uint7".to_string(),
+                    to: "uint8".to_string(),
+                },
+                TypeFixup {
+                    entry_type: "function".to_string(),
+                    entry_name: Some("f".to_string()),
+                    from: "int12[]".to_string(),
+                    to: "int16[]".to_string(),
+                },
+                TypeFixup {
+                    entry_type: "constructor".to_string(),
+                    entry_name: None,
+                    from: "uint255".to_string(),
+                    to: "uint256".to_string(),
+                },
+            ]
+        );
+
+        // plain deserialization is lenient too
+        let de_abi: Abi = serde_json::from_str(abi_json).expect("ABI deserialized");
+        assert_eq!(de_abi, abi);
+
+        let (_, fixups) = Abi::from_str_with_fixups(TEST_ABI_V1, &ParseOptions::default())
+            .expect("from_str_with_fixups failed");
+        assert!(fixups.is_empty());
+
+        let err = Abi::from_str_with(abi_json, &ParseOptions::strict())
+            .expect_err("from_str_with succeeded");
+        assert!(err
+            .to_string()
+            .contains("invalid type uint7: int sizes must be multiples of 8 between 8 and 256"));
+    }
+
+    #[test]
+    fn parse_from_str() {
+        let abi: Abi = TEST_ABI_V1.parse().expect("parse failed");
+        assert_eq!(abi, serde_json::from_str(TEST_ABI_V1).unwrap());
+
+        let err = "[{".parse::<Abi>().expect_err("parse succeeded");
+        assert!(matches!(
+            err.downcast_ref::<AbiError>(),
+            Some(AbiError::Json { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn unknown_entry_types() {
+        let abi_json = r#"[
+            {"type":"function","name":"f","inputs":[],"stateMutability":"view"},
+            {"type":"modifier","name":"onlyOwner","inputs":[]}
+        ]"#;
+
+        let abi: Abi = serde_json::from_str(abi_json).expect("ABI deserialized");
+        assert_eq!(abi.functions.len(), 1);
+
+        let abi =
+            Abi::from_str_with(abi_json, &ParseOptions::default()).expect("from_str_with failed");
+        assert_eq!(abi.functions.len(), 1);
+
+        let err = Abi::from_str_with(abi_json, &ParseOptions::strict())
+            .expect_err("from_str_with succeeded");
+        assert!(err.to_string().contains("invalid ABI entry type: modifier"));
+
+        assert!(Abi::from_str_with("[] []", &ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn precomputed_signatures() {
+        let abi_json = r#"[
+            {"type":"function","name":"transfer","inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"}],"stateMutability":"nonpayable","signature":"transfer(address,uint256)","gas":30000},
+            {"type":"function","name":"f","inputs":[],"stateMutability":"view","signature":"0x26121FF0"},
+            {"type":"event","name":"E","inputs":[],"anonymous":false,"signature":"0x92bbf6e823a631f3c8e09b1c8df90f378fb56f7fbc9701827e1ff8aad7f6a028"}
+        ]"#;
+
+        let (_, signatures) = Abi::from_str_with_signatures(abi_json, &ParseOptions::strict())
+            .expect("from_str_with_signatures failed");
+        assert_eq!(
+            signatures
+                .iter()
+                .map(|s| (s.entry_name.as_str(), s.signature.as_str(), s.matches))
+                .collect::<Vec<_>>(),
+            vec![
+                ("transfer", "transfer(address,uint256)", true),
+                ("f", "0x26121FF0", true),
+                (
+                    "E",
+                    "0x92bbf6e823a631f3c8e09b1c8df90f378fb56f7fbc9701827e1ff8aad7f6a028",
+                    true
+                )
+            ]
+        );
+
+        let abi_json = r#"[
+            {"type":"function","name":"transfer","inputs":[{"name":"to","type":"address"}],"stateMutability":"nonpayable","signature":"transfer(address,uint256)"}
+        ]"#;
+
+        // non strict parsing reports the mismatching signature for callers to check
+        let (abi, signatures) = Abi::from_str_with_signatures(abi_json, &ParseOptions::default())
+            .expect("from_str_with_signatures failed");
+        assert_eq!(abi.functions[0].signature(), "transfer(address)");
+        assert_eq!(
+            signatures,
+            vec![ProvidedSignature {
+                entry_type: "function".to_string(),
+                entry_name: "transfer".to_string(),
+                signature: "transfer(address,uint256)".to_string(),
+                matches: false,
+            }]
+        );
+
+        let ser_abi = serde_json::to_string(&abi).unwrap();
+        assert!(!ser_abi.contains("signature"));
+        assert_eq!(Abi::from_str(&ser_abi).unwrap(), abi);
+
+        let err = Abi::from_str_with(abi_json, &ParseOptions::strict())
+            .expect_err("from_str_with succeeded");
+        assert!(err.to_string().contains(
+            "signature mismatch: computed transfer(address), ABI entry has transfer(address,uint256)"
+        ));
+
+        let abi_json = r#"[{"type":"function","name":"f","inputs":[],"stateMutability":"view","signature":"0x00000000"}]"#;
+        assert!(Abi::from_str_with(abi_json, &ParseOptions::strict()).is_err());
+    }
+
+    #[test]
+    fn parse_max_depth() {
+        let abi_json = format!(
+            r#"[{{"type":"function","name":"f","inputs":[{{"name":"x","type":"uint{}"}}],"stateMutability":"view"}}]"#,
+            "[]".repeat(8)
+        );
+
+        assert!(Abi::from_str_with(&abi_json, &ParseOptions::default()).is_ok());
+
+        let opts = ParseOptions {
+            max_depth: 8,
+            ..ParseOptions::default()
+        };
+        let err = Abi::from_str_with(&abi_json, &opts).expect_err("from_str_with succeeded");
+        match err.downcast_ref::<AbiError>() {
+            Some(AbiError::Json { message, .. }) => {
+                assert_eq!(message, "type nesting exceeds the maximum depth of 8");
+            }
+            _ => panic!("unexpected error: {}", err),
+        }
+    }
+
     #[test]
     fn test_serde() {
         let abi: Abi = serde_json::from_str(TEST_ABI_V1).unwrap();
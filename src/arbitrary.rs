@@ -0,0 +1,116 @@
+//! [`proptest`] strategies generating arbitrary well-formed types and values.
+//!
+//! Available with the `proptest` feature.
+
+use ethereum_types::{H160, U256};
+use proptest::{collection, prelude::*};
+
+use crate::{Type, Value};
+
+/// Strategy generating arbitrary types, including nested arrays and tuples.
+pub fn any_type() -> impl Strategy<Value = Type> {
+    let leaf = prop_oneof![
+        (1..=32usize).prop_map(|n| Type::Uint(n * 8)),
+        (1..=32usize).prop_map(|n| Type::Int(n * 8)),
+        Just(Type::Address),
+        Just(Type::Bool),
+        (1..=32usize).prop_map(Type::FixedBytes),
+        Just(Type::String),
+        Just(Type::Bytes),
+    ];
+
+    leaf.prop_recursive(3, 16, 4, |inner| {
+        prop_oneof![
+            (inner.clone(), 1..4usize).prop_map(|(ty, size)| Type::FixedArray(Box::new(ty), size)),
+            inner.clone().prop_map(|ty| Type::Array(Box::new(ty))),
+            collection::vec(inner, 1..4).prop_map(|tys| {
+                Type::Tuple(
+                    tys.into_iter()
+                        .enumerate()
+                        .map(|(i, ty)| (format!("f{}", i), ty))
+                        .collect(),
+                )
+            }),
+        ]
+    })
+}
+
+/// Strategy generating arbitrary values conforming to the given type.
+///
+/// Int values are generated in their sign extended 256 bits two's complement form.
+pub fn any_value(ty: &Type) -> BoxedStrategy<Value> {
+    match ty {
+        Type::Uint(size) => {
+            let size = *size;
+
+            any::<[u8; 32]>()
+                .prop_map(move |bs| Value::Uint(U256::from_big_endian(&bs) >> (256 - size), size))
+                .boxed()
+        }
+
+        Type::Int(size) => {
+            let size = *size;
+
+            any::<[u8; 32]>()
+                .prop_map(move |bs| {
+                    // arithmetic shift right to sign extend the value to 256 bits
+                    let word = U256::from_big_endian(&bs);
+                    let shifted = word >> (256 - size);
+                    let value = if word.bit(255) && size < 256 {
+                        shifted | (U256::MAX << size)
+                    } else {
+                        shifted
+                    };
+
+                    Value::Int(value, size)
+                })
+                .boxed()
+        }
+
+        Type::Address => any::<[u8; 20]>()
+            .prop_map(|bs| Value::Address(H160::from(bs)))
+            .boxed(),
+
+        Type::Bool => any::<bool>().prop_map(Value::Bool).boxed(),
+
+        Type::FixedBytes(size) => collection::vec(any::<u8>(), *size)
+            .prop_map(Value::FixedBytes)
+            .boxed(),
+
+        Type::String => any::<String>().prop_map(Value::String).boxed(),
+
+        Type::Bytes => collection::vec(any::<u8>(), 0..64)
+            .prop_map(Value::Bytes)
+            .boxed(),
+
+        Type::FixedArray(ty, size) => {
+            let elem_ty = *ty.clone();
+
+            collection::vec(any_value(ty), *size)
+                .prop_map(move |values| Value::FixedArray(values, elem_ty.clone()))
+                .boxed()
+        }
+
+        Type::Array(ty) => {
+            let elem_ty = *ty.clone();
+
+            collection::vec(any_value(ty), 0..4)
+                .prop_map(move |values| Value::Array(values, elem_ty.clone()))
+                .boxed()
+        }
+
+        Type::Tuple(tys) => {
+            let names: Vec<_> = tys.iter().map(|(name, _)| name.clone()).collect();
+            let values: Vec<_> = tys.iter().map(|(_, ty)| any_value(ty)).collect();
+
+            values
+                .prop_map(move |values| Value::Tuple(names.iter().cloned().zip(values).collect()))
+                .boxed()
+        }
+    }
+}
+
+/// Strategy generating an arbitrary type along with a value conforming to it.
+pub fn any_type_and_value() -> impl Strategy<Value = (Type, Value)> {
+    any_type().prop_flat_map(|ty| (Just(ty.clone()), any_value(&ty)))
+}
@@ -0,0 +1,301 @@
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use ethereum_types::{H160, U256};
+
+use crate::{AbiError, Type, Value};
+
+/// Conversion of Rust values into ABI values.
+///
+/// ```
+/// use ethereum_abi::{AbiEncode, Type, Value};
+/// use ethereum_types::{H160, U256};
+///
+/// struct Transfer {
+///     to: H160,
+///     amount: U256,
+/// }
+///
+/// impl AbiEncode for Transfer {
+///     fn abi_type() -> Type {
+///         <(H160, U256)>::abi_type()
+///     }
+///
+///     fn to_value(&self) -> Value {
+///         (self.to, self.amount).to_value()
+///     }
+/// }
+///
+/// let transfer = Transfer { to: H160::zero(), amount: U256::from(1) };
+/// let encoded = Value::encode(&[transfer.to_value()]);
+///
+/// assert_eq!(encoded.len(), 64);
+/// ```
+pub trait AbiEncode {
+    /// Returns the ABI type values of this type convert to.
+    ///
+    /// Needed to type empty arrays.
+    fn abi_type() -> Type;
+
+    /// Converts the value into an ABI value.
+    fn to_value(&self) -> Value;
+}
+
+/// Conversion of ABI values into Rust values.
+pub trait AbiDecode: Sized {
+    /// Converts an ABI value, failing with [`AbiError::UnexpectedValue`] if it
+    /// doesn't have a compatible type.
+    fn from_value(v: &Value) -> Result<Self, AbiError>;
+}
+
+// Builds the error for a value that doesn't convert to `T`.
+fn unexpected<T: AbiEncode>(v: &Value) -> AbiError {
+    AbiError::UnexpectedValue {
+        expected: T::abi_type(),
+        got: v.type_of(),
+    }
+}
+
+impl AbiEncode for U256 {
+    fn abi_type() -> Type {
+        Type::Uint(256)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Uint(*self, 256)
+    }
+}
+
+/// Accepts `uint` values of any size.
+impl AbiDecode for U256 {
+    fn from_value(v: &Value) -> Result<Self, AbiError> {
+        match v {
+            Value::Uint(i, _) => Ok(*i),
+            _ => Err(unexpected::<Self>(v)),
+        }
+    }
+}
+
+impl AbiEncode for H160 {
+    fn abi_type() -> Type {
+        Type::Address
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Address(*self)
+    }
+}
+
+impl AbiDecode for H160 {
+    fn from_value(v: &Value) -> Result<Self, AbiError> {
+        match v {
+            Value::Address(addr) => Ok(*addr),
+            _ => Err(unexpected::<Self>(v)),
+        }
+    }
+}
+
+impl AbiEncode for bool {
+    fn abi_type() -> Type {
+        Type::Bool
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+}
+
+impl AbiDecode for bool {
+    fn from_value(v: &Value) -> Result<Self, AbiError> {
+        match v {
+            Value::Bool(b) => Ok(*b),
+            _ => Err(unexpected::<Self>(v)),
+        }
+    }
+}
+
+impl AbiEncode for String {
+    fn abi_type() -> Type {
+        Type::String
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl AbiDecode for String {
+    fn from_value(v: &Value) -> Result<Self, AbiError> {
+        match v {
+            Value::String(s) => Ok(s.clone()),
+            _ => Err(unexpected::<Self>(v)),
+        }
+    }
+}
+
+/// Converts to `bytes` rather than `uint8[]`.
+impl AbiEncode for Vec<u8> {
+    fn abi_type() -> Type {
+        Type::Bytes
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Bytes(self.clone())
+    }
+}
+
+/// Accepts `bytes` and `bytesN` values.
+impl AbiDecode for Vec<u8> {
+    fn from_value(v: &Value) -> Result<Self, AbiError> {
+        match v {
+            Value::Bytes(bytes) | Value::FixedBytes(bytes) => Ok(bytes.clone()),
+            _ => Err(unexpected::<Self>(v)),
+        }
+    }
+}
+
+impl<T: AbiEncode> AbiEncode for Vec<T> {
+    fn abi_type() -> Type {
+        Type::Array(Box::new(T::abi_type()))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Array(self.iter().map(T::to_value).collect(), T::abi_type())
+    }
+}
+
+/// Accepts both dynamic and fixed size arrays.
+impl<T: AbiEncode + AbiDecode> AbiDecode for Vec<T> {
+    fn from_value(v: &Value) -> Result<Self, AbiError> {
+        match v {
+            Value::Array(values, _) | Value::FixedArray(values, _) => {
+                values.iter().map(T::from_value).collect()
+            }
+            _ => Err(unexpected::<Self>(v)),
+        }
+    }
+}
+
+macro_rules! impl_tuple {
+    ($len:expr => $($t:ident $i:tt),+) => {
+        impl<$($t: AbiEncode),+> AbiEncode for ($($t,)+) {
+            fn abi_type() -> Type {
+                Type::Tuple(vec![$((String::new(), $t::abi_type())),+])
+            }
+
+            fn to_value(&self) -> Value {
+                Value::Tuple(vec![$((String::new(), self.$i.to_value())),+])
+            }
+        }
+
+        impl<$($t: AbiEncode + AbiDecode),+> AbiDecode for ($($t,)+) {
+            fn from_value(v: &Value) -> Result<Self, AbiError> {
+                match v {
+                    Value::Tuple(values) if values.len() == $len => {
+                        Ok(($($t::from_value(&values[$i].1)?,)+))
+                    }
+                    _ => Err(unexpected::<Self>(v)),
+                }
+            }
+        }
+    };
+}
+
+impl_tuple!(1 => A 0);
+impl_tuple!(2 => A 0, B 1);
+impl_tuple!(3 => A 0, B 1, C 2);
+impl_tuple!(4 => A 0, B 1, C 2, D 3);
+impl_tuple!(5 => A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(6 => A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(7 => A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(8 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuple!(9 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuple!(10 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuple!(11 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuple!(12 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn primitives_round_trip() {
+        let addr = H160::from_low_u64_be(0xaa);
+
+        assert_eq!(U256::from(7).to_value(), Value::Uint(U256::from(7), 256));
+        assert_eq!(addr.to_value(), Value::Address(addr));
+        assert_eq!(true.to_value(), Value::Bool(true));
+        assert_eq!(
+            "abc".to_string().to_value(),
+            Value::String("abc".to_string())
+        );
+        assert_eq!(vec![1u8, 2].to_value(), Value::Bytes(vec![1, 2]));
+
+        assert_eq!(
+            U256::from_value(&Value::Uint(U256::from(7), 8)),
+            Ok(U256::from(7))
+        );
+        assert_eq!(H160::from_value(&Value::Address(addr)), Ok(addr));
+        assert_eq!(bool::from_value(&Value::Bool(true)), Ok(true));
+        assert_eq!(
+            String::from_value(&Value::String("abc".to_string())),
+            Ok("abc".to_string())
+        );
+        assert_eq!(
+            Vec::<u8>::from_value(&Value::FixedBytes(vec![1, 2])),
+            Ok(vec![1, 2])
+        );
+
+        assert_eq!(
+            bool::from_value(&Value::Address(addr)),
+            Err(AbiError::UnexpectedValue {
+                expected: Type::Bool,
+                got: Type::Address
+            })
+        );
+    }
+
+    #[test]
+    fn arrays_and_tuples() {
+        let values = vec![U256::from(1), U256::from(2)];
+        let value = values.to_value();
+        assert_eq!(
+            value,
+            Value::Array(
+                vec![
+                    Value::Uint(U256::from(1), 256),
+                    Value::Uint(U256::from(2), 256)
+                ],
+                Type::Uint(256)
+            )
+        );
+        assert_eq!(Vec::<U256>::from_value(&value), Ok(values));
+
+        assert_eq!(
+            Vec::<String>::new().to_value(),
+            Value::Array(vec![], Type::String)
+        );
+
+        let tuple = (H160::zero(), U256::from(3), vec![true]);
+        let value = tuple.to_value();
+        assert_eq!(value.type_of(), <(H160, U256, Vec<bool>)>::abi_type());
+        assert_eq!(<(H160, U256, Vec<bool>)>::from_value(&value), Ok(tuple));
+
+        assert!(<(H160, U256)>::from_value(&value).is_err());
+        assert!(<(bool, U256, Vec<bool>)>::from_value(&value).is_err());
+    }
+
+    #[test]
+    fn encode_tuple_value() {
+        let value = (U256::from(1), "abc".to_string()).to_value();
+
+        let encoded = Value::encode(core::slice::from_ref(&value));
+        let decoded = Value::decode_from_slice(&encoded, &[value.type_of()])
+            .expect("decode_from_slice failed");
+
+        assert_eq!(
+            <(U256, String)>::from_value(&decoded[0]),
+            Ok((U256::from(1), "abc".to_string()))
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use alloc::string::{String, ToString};
+use ethereum_types::U256;
+
+use crate::Type;
+
+/// ABI errors that callers may want to handle programmatically.
+///
+/// Fallible functions in this crate return [`anyhow::Error`], from which these can be
+/// recovered with [`anyhow::Error::downcast_ref`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiError {
+    /// A string value is not valid UTF-8.
+    InvalidUtf8 {
+        /// Input position of the first invalid byte.
+        offset: usize,
+    },
+    /// Invalid ABI JSON.
+    Json {
+        /// Error description.
+        message: String,
+        /// Line of the input where the error was found (starting at 1).
+        line: usize,
+        /// Column of the input where the error was found (starting at 1).
+        column: usize,
+    },
+    /// A type is nested deeper than allowed.
+    MaxDepthExceeded {
+        /// Maximum allowed nesting depth.
+        max_depth: usize,
+    },
+    /// Decoding would produce more values than allowed.
+    MaxElementsExceeded {
+        /// Maximum allowed number of values.
+        max_elements: usize,
+    },
+    /// A decoded offset or length does not fit in `usize`.
+    OffsetTooLarge {
+        /// The decoded offset or length.
+        value: U256,
+    },
+    /// A dynamic value's offset points into the head region of the values holding it.
+    OffsetIntoHead {
+        /// The decoded offset.
+        offset: usize,
+        /// Size of the head region the offset is relative to.
+        head_size: usize,
+    },
+    /// The number of log topics doesn't match the number of topics the event emits.
+    TopicCountMismatch {
+        /// Expected number of topics.
+        expected: usize,
+        /// Number of topics given.
+        got: usize,
+    },
+    /// A value does not have the type a conversion expects.
+    UnexpectedValue {
+        /// Type the conversion expects.
+        expected: Type,
+        /// Type of the given value.
+        got: Type,
+    },
+    /// No function in the ABI matches the input's selector.
+    UnknownSelector {
+        /// Selector taken from the input.
+        selector: [u8; 4],
+    },
+}
+
+impl core::fmt::Display for AbiError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AbiError::InvalidUtf8 { offset } => {
+                write!(f, "invalid UTF-8 sequence in string at offset {}", offset)
+            }
+            AbiError::Json {
+                message,
+                line,
+                column,
+            } => write!(
+                f,
+                "invalid ABI JSON at line {} column {}: {}",
+                line, column, message
+            ),
+            AbiError::MaxDepthExceeded { max_depth } => {
+                write!(f, "type nesting exceeds the maximum depth of {}", max_depth)
+            }
+            AbiError::MaxElementsExceeded { max_elements } => {
+                write!(
+                    f,
+                    "input holds more than the maximum of {} values",
+                    max_elements
+                )
+            }
+            AbiError::OffsetTooLarge { value } => {
+                write!(f, "offset or length {} does not fit in usize", value)
+            }
+            AbiError::OffsetIntoHead { offset, head_size } => write!(
+                f,
+                "offset {} points into the {} bytes head region",
+                offset, head_size
+            ),
+            AbiError::TopicCountMismatch { expected, got } => {
+                write!(f, "expected {} log topics, got {}", expected, got)
+            }
+            AbiError::UnexpectedValue { expected, got } => {
+                write!(f, "expected {} value, got {}", expected, got)
+            }
+            AbiError::UnknownSelector { selector } => write!(
+                f,
+                "ABI function not found for selector 0x{}",
+                hex::encode(selector)
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AbiError {}
+
+// Without std, anyhow only converts errors implementing `core::error::Error`, which
+// needs Rust 1.81.
+#[cfg(not(feature = "std"))]
+impl core::error::Error for AbiError {}
+
+impl From<serde_json::Error> for AbiError {
+    fn from(err: serde_json::Error) -> Self {
+        let message = err.to_string();
+
+        // serde_json appends the error position to its messages.
+        let message = match message.rfind(" at line ") {
+            Some(i) => message[..i].to_string(),
+            None => message,
+        };
+
+        AbiError::Json {
+            message,
+            line: err.line(),
+            column: err.column(),
+        }
+    }
+}
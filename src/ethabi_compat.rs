@@ -0,0 +1,240 @@
+//! Conversions between this crate's types and values and [`ethabi`]'s
+//! [`ParamType`] and [`Token`].
+//!
+//! Available with the `ethabi-compat` feature.
+//!
+//! Not everything maps cleanly:
+//!
+//! - tuple component names are dropped when converting to ethabi, and come back
+//!   empty;
+//! - tokens don't carry int sizes nor array element types, so converting a [`Token`]
+//!   into a [`Value`] needs the value's [`Type`] (see [`Value::from_token`]);
+//! - both crates represent signed ints as sign extended 256 bits two's complement
+//!   words, so `int<M>` values are passed through as is.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use anyhow::{anyhow, Result};
+use ethabi::{ParamType, Token};
+
+use crate::{Type, Value};
+
+impl From<Type> for ParamType {
+    fn from(ty: Type) -> Self {
+        match ty {
+            Type::Uint(size) => ParamType::Uint(size),
+            Type::Int(size) => ParamType::Int(size),
+            Type::Address => ParamType::Address,
+            Type::Bool => ParamType::Bool,
+            Type::FixedBytes(size) => ParamType::FixedBytes(size),
+            Type::FixedArray(ty, size) => ParamType::FixedArray(Box::new((*ty).into()), size),
+            Type::String => ParamType::String,
+            Type::Bytes => ParamType::Bytes,
+            Type::Array(ty) => ParamType::Array(Box::new((*ty).into())),
+            Type::Tuple(tys) => {
+                ParamType::Tuple(tys.into_iter().map(|(_, ty)| ty.into()).collect())
+            }
+        }
+    }
+}
+
+impl From<ParamType> for Type {
+    fn from(ty: ParamType) -> Self {
+        match ty {
+            ParamType::Uint(size) => Type::Uint(size),
+            ParamType::Int(size) => Type::Int(size),
+            ParamType::Address => Type::Address,
+            ParamType::Bool => Type::Bool,
+            ParamType::FixedBytes(size) => Type::FixedBytes(size),
+            ParamType::FixedArray(ty, size) => Type::FixedArray(Box::new((*ty).into()), size),
+            ParamType::String => Type::String,
+            ParamType::Bytes => Type::Bytes,
+            ParamType::Array(ty) => Type::Array(Box::new((*ty).into())),
+            ParamType::Tuple(tys) => Type::Tuple(
+                tys.into_iter()
+                    .map(|ty| (String::new(), ty.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<Value> for Token {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Uint(i, _) => Token::Uint(i),
+            Value::Int(i, _) => Token::Int(i),
+            Value::Address(addr) => Token::Address(addr),
+            Value::Bool(b) => Token::Bool(b),
+            Value::FixedBytes(bytes) => Token::FixedBytes(bytes),
+            Value::FixedArray(values, _) => {
+                Token::FixedArray(values.into_iter().map(Into::into).collect())
+            }
+            Value::String(s) => Token::String(s),
+            Value::Bytes(bytes) => Token::Bytes(bytes),
+            Value::Array(values, _) => Token::Array(values.into_iter().map(Into::into).collect()),
+            Value::Tuple(values) => {
+                Token::Tuple(values.into_iter().map(|(_, value)| value.into()).collect())
+            }
+        }
+    }
+}
+
+impl Value {
+    /// Converts an ethabi token into a value of the given type.
+    ///
+    /// Fails if the token doesn't conform to the type. Tuple component names are
+    /// taken from the type.
+    pub fn from_token(token: Token, ty: &Type) -> Result<Value> {
+        let value = match (token, ty) {
+            (Token::Uint(i), Type::Uint(size)) => Value::uint(i, *size)?,
+
+            (Token::Int(i), Type::Int(size)) if Value::is_sign_extended(i, *size) => {
+                Value::Int(i, *size)
+            }
+
+            (Token::Address(addr), Type::Address) => Value::Address(addr),
+
+            (Token::Bool(b), Type::Bool) => Value::Bool(b),
+
+            (Token::FixedBytes(bytes), Type::FixedBytes(size)) => Value::fixed_bytes(bytes, *size)?,
+
+            (Token::FixedArray(tokens), Type::FixedArray(elem_ty, size))
+                if tokens.len() == *size =>
+            {
+                Value::FixedArray(Self::from_tokens(tokens, elem_ty)?, *elem_ty.clone())
+            }
+
+            (Token::String(s), Type::String) => Value::String(s),
+
+            (Token::Bytes(bytes), Type::Bytes) => Value::Bytes(bytes),
+
+            (Token::Array(tokens), Type::Array(elem_ty)) => {
+                Value::Array(Self::from_tokens(tokens, elem_ty)?, *elem_ty.clone())
+            }
+
+            (Token::Tuple(tokens), Type::Tuple(tys)) if tokens.len() == tys.len() => Value::Tuple(
+                tokens
+                    .into_iter()
+                    .zip(tys)
+                    .map(|(token, (name, ty))| Ok((name.clone(), Value::from_token(token, ty)?)))
+                    .collect::<Result<_>>()?,
+            ),
+
+            (token, ty) => return Err(anyhow!("token {} does not conform to type {}", token, ty)),
+        };
+
+        Ok(value)
+    }
+
+    fn from_tokens(tokens: Vec<Token>, ty: &Type) -> Result<Vec<Value>> {
+        tokens
+            .into_iter()
+            .map(|token| Value::from_token(token, ty))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use ethereum_types::{H160, U256};
+    use pretty_assertions::assert_eq;
+
+    fn test_type() -> Type {
+        Type::Tuple(vec![
+            ("a".to_string(), Type::Int(8)),
+            (
+                "b".to_string(),
+                Type::Array(Box::new(Type::FixedArray(Box::new(Type::Address), 2))),
+            ),
+            ("c".to_string(), Type::FixedBytes(2)),
+            ("d".to_string(), Type::String),
+        ])
+    }
+
+    #[test]
+    fn type_to_param_type() {
+        let param_ty: ParamType = test_type().into();
+
+        assert_eq!(
+            param_ty,
+            ParamType::Tuple(vec![
+                ParamType::Int(8),
+                ParamType::Array(Box::new(ParamType::FixedArray(
+                    Box::new(ParamType::Address),
+                    2
+                ))),
+                ParamType::FixedBytes(2),
+                ParamType::String,
+            ])
+        );
+
+        let ty: Type = param_ty.into();
+        assert_eq!(ty.to_string(), test_type().to_string());
+    }
+
+    #[test]
+    fn value_to_token() {
+        let addr = H160::random();
+
+        let value = Value::Tuple(vec![
+            ("a".to_string(), Value::Int(U256::MAX, 8)),
+            (
+                "b".to_string(),
+                Value::Array(
+                    vec![Value::FixedArray(
+                        vec![Value::Address(addr), Value::Address(addr)],
+                        Type::Address,
+                    )],
+                    Type::FixedArray(Box::new(Type::Address), 2),
+                ),
+            ),
+            ("c".to_string(), Value::FixedBytes(vec![1, 2])),
+            ("d".to_string(), Value::String("x".to_string())),
+        ]);
+
+        let token: Token = value.clone().into();
+
+        assert_eq!(
+            token,
+            Token::Tuple(vec![
+                Token::Int(U256::MAX),
+                Token::Array(vec![Token::FixedArray(vec![
+                    Token::Address(addr),
+                    Token::Address(addr)
+                ])]),
+                Token::FixedBytes(vec![1, 2]),
+                Token::String("x".to_string()),
+            ])
+        );
+
+        assert_eq!(
+            ethabi::encode(std::slice::from_ref(&token)),
+            Value::encode(std::slice::from_ref(&value))
+        );
+
+        assert_eq!(
+            Value::from_token(token, &test_type()).expect("from_token failed"),
+            value
+        );
+    }
+
+    #[test]
+    fn token_to_value_type_mismatch() {
+        assert!(Value::from_token(Token::Bool(true), &Type::Uint(8)).is_err());
+        assert!(Value::from_token(Token::Uint(U256::from(256)), &Type::Uint(8)).is_err());
+        assert!(Value::from_token(Token::Int(U256::from(128)), &Type::Int(8)).is_err());
+        assert!(Value::from_token(Token::FixedBytes(vec![1]), &Type::FixedBytes(2)).is_err());
+        assert!(Value::from_token(
+            Token::FixedArray(vec![Token::Bool(true)]),
+            &Type::FixedArray(Box::new(Type::Bool), 2)
+        )
+        .is_err());
+        assert!(Value::from_token(
+            Token::Array(vec![Token::Bool(true), Token::String("x".to_string())]),
+            &Type::Array(Box::new(Type::Bool))
+        )
+        .is_err());
+    }
+}
@@ -1,8 +1,11 @@
+use alloc::{collections::VecDeque, format, string::String, vec, vec::Vec};
 use anyhow::{anyhow, Result};
 use ethereum_types::H256;
-use std::collections::VecDeque;
 
-use crate::{DecodedParams, Param, Type, Value};
+use crate::{
+    keccak::{self, Keccak256, TinyKeccak},
+    util, AbiError, DecodedParams, Param, Type, Value,
+};
 
 /// Contract Error Definition
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -13,6 +16,31 @@ pub struct Error {
     pub inputs: Vec<Param>,
 }
 
+impl Error {
+    /// Returns the error's signature.
+    pub fn signature(&self) -> String {
+        format!(
+            "{}({})",
+            self.name,
+            self.inputs
+                .iter()
+                .map(|param| param.type_.canonical_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+
+    /// Computes the error's selector.
+    pub fn selector(&self) -> [u8; 4] {
+        self.selector_with::<TinyKeccak>()
+    }
+
+    /// Computes the error's selector using the given Keccak-256 implementation.
+    pub fn selector_with<H: Keccak256>(&self) -> [u8; 4] {
+        keccak::selector::<H>(&self.signature())
+    }
+}
+
 /// Contract event definition.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Event {
@@ -25,44 +53,131 @@ pub struct Event {
 }
 
 impl Event {
+    /// Creates a non anonymous event with the given name and no inputs.
+    ///
+    /// ```
+    /// use ethereum_abi::{Event, Type};
+    ///
+    /// let e = Event::new("Transfer")
+    ///     .input("from", Type::Address, true)
+    ///     .input("to", Type::Address, true)
+    ///     .input("value", Type::Uint(256), false);
+    ///
+    /// assert_eq!(e.signature(), "Transfer(address,address,uint256)");
+    /// ```
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            inputs: vec![],
+            anonymous: false,
+        }
+    }
+
+    /// Appends an input parameter to the event.
+    pub fn input(mut self, name: impl Into<String>, type_: Type, indexed: bool) -> Self {
+        self.inputs.push(Param {
+            name: name.into(),
+            type_,
+            indexed: Some(indexed),
+        });
+        self
+    }
+
+    /// Sets whether the event is anonymous or not.
+    pub fn anonymous(mut self, anonymous: bool) -> Self {
+        self.anonymous = anonymous;
+        self
+    }
+
+    /// Returns the event's indexed inputs, in declaration order.
+    ///
+    /// These are the inputs encoded as log topics.
+    pub fn indexed_inputs(&self) -> impl Iterator<Item = &Param> {
+        self.inputs
+            .iter()
+            .filter(|input| input.indexed.unwrap_or(false))
+    }
+
+    /// Returns the event's non indexed inputs, in declaration order.
+    ///
+    /// These are the inputs encoded as the log data.
+    pub fn non_indexed_inputs(&self) -> impl Iterator<Item = &Param> {
+        self.inputs
+            .iter()
+            .filter(|input| !input.indexed.unwrap_or(false))
+    }
+
     /// Returns the event's signature.
+    ///
+    /// The signature lists every input type, whether indexed or not, so an input's
+    /// indexed flag doesn't affect the event's topic hash.
     pub fn signature(&self) -> String {
         format!(
             "{}({})",
             self.name,
             self.inputs
                 .iter()
-                .map(|param| param.type_.to_string())
+                .map(|param| param.type_.canonical_string())
                 .collect::<Vec<_>>()
                 .join(",")
         )
     }
 
     /// Compute the event's topic hash
+    ///
+    /// The hash is computed for anonymous events too, even though they don't emit it
+    /// as their first topic, so it shouldn't be matched against their logs.
     pub fn topic(&self) -> H256 {
-        use tiny_keccak::{Hasher, Keccak};
-
-        let mut keccak_out = [0u8; 32];
-        let mut hasher = Keccak::v256();
-        hasher.update(self.signature().as_bytes());
-        hasher.finalize(&mut keccak_out);
+        self.topic_with::<TinyKeccak>()
+    }
 
-        H256::from_slice(&keccak_out)
+    /// Compute the event's topic hash using the given Keccak-256 implementation.
+    pub fn topic_with<H: Keccak256>(&self) -> H256 {
+        H256::from(H::keccak256(self.signature().as_bytes()))
     }
 
     /// Decode event params from a log's topics and data.
-    pub fn decode_data_from_slice(
+    ///
+    /// The topics are expected to include the event topic hash unless the event is
+    /// anonymous.
+    pub fn decode_data_from_slice(&self, topics: &[H256], data: &[u8]) -> Result<DecodedParams> {
+        self.decode_log(topics, data, !self.anonymous)
+    }
+
+    /// Decode event params from a log's topics and hex encoded data, with or without
+    /// a `0x` prefix, as returned by JSON-RPC nodes.
+    pub fn decode_data_from_hex(&self, topics: &[H256], data: &str) -> Result<DecodedParams> {
+        let data = util::hex_to_bytes(data)?;
+
+        self.decode_data_from_slice(topics, &data)
+    }
+
+    /// Decode event params from a log's topics and data, stating whether the topics
+    /// include a leading topic hash entry that must be skipped.
+    ///
+    /// Fails with [`AbiError::TopicCountMismatch`] if the number of topics doesn't
+    /// match the number of indexed params (plus one if `has_topic0` is set).
+    pub fn decode_log(
         &self,
         mut topics: &[H256],
         data: &[u8],
+        has_topic0: bool,
     ) -> Result<DecodedParams> {
+        let expected = self.indexed_inputs().count() + usize::from(has_topic0);
+
+        if topics.len() != expected {
+            return Err(AbiError::TopicCountMismatch {
+                expected,
+                got: topics.len(),
+            }
+            .into());
+        }
+
         // strip event topic from the topics array
         // so that we end up with only the values we
         // need to decode
-        if !self.anonymous {
-            topics = topics
-                .get(1..)
-                .ok_or_else(|| anyhow!("missing event topic"))?;
+        if has_topic0 {
+            topics = &topics[1..];
         }
 
         let mut topics_values = VecDeque::from(topics.to_vec());
@@ -70,9 +185,7 @@ impl Event {
         let mut data_values = VecDeque::from(Value::decode_from_slice(
             data,
             &self
-                .inputs
-                .iter()
-                .filter(|input| !input.indexed.unwrap_or(false))
+                .non_indexed_inputs()
                 .map(|input| input.type_.clone())
                 .collect::<Vec<_>>(),
         )?);
@@ -80,20 +193,9 @@ impl Event {
         let mut decoded = vec![];
         for input in self.inputs.iter().cloned() {
             let decoded_value = if input.indexed.unwrap_or(false) {
-                let val = topics_values
-                    .pop_front()
-                    .ok_or_else(|| anyhow!("insufficient topics entries"))?;
+                let topic = topics_values.pop_front().expect("topics count checked");
 
-                let bytes = val.to_fixed_bytes().to_vec();
-
-                if Self::is_encoded_to_keccak(&input.type_) {
-                    Ok(Value::FixedBytes(bytes))
-                } else {
-                    Value::decode_from_slice(&bytes, &[input.type_.clone()])?
-                        .first()
-                        .ok_or_else(|| anyhow!("no value decoded from topics entry"))
-                        .map(Clone::clone)
-                }
+                Self::decode_topic(&input, topic)
             } else {
                 data_values
                     .pop_front()
@@ -106,6 +208,194 @@ impl Event {
         Ok(DecodedParams::from(decoded))
     }
 
+    /// Decode only the indexed event params from a log's topics, without touching
+    /// the log data.
+    ///
+    /// The topics are expected to include the event topic hash unless the event is
+    /// anonymous. Indexed params of dynamic or composite types are decoded as the
+    /// 32 bytes hash stored in their topic.
+    ///
+    /// Fails with [`AbiError::TopicCountMismatch`] if the number of topics doesn't
+    /// match the number of indexed params.
+    pub fn decode_topics(&self, mut topics: &[H256]) -> Result<Vec<(Param, Value)>> {
+        let expected = self.indexed_inputs().count() + usize::from(!self.anonymous);
+
+        if topics.len() != expected {
+            return Err(AbiError::TopicCountMismatch {
+                expected,
+                got: topics.len(),
+            }
+            .into());
+        }
+
+        if !self.anonymous {
+            topics = &topics[1..];
+        }
+
+        self.indexed_inputs()
+            .zip(topics)
+            .map(|(input, topic)| Ok((input.clone(), Self::decode_topic(input, *topic)?)))
+            .collect()
+    }
+
+    // Decodes an indexed param value from its log topic.
+    fn decode_topic(input: &Param, topic: H256) -> Result<Value> {
+        let bytes = topic.to_fixed_bytes().to_vec();
+
+        if Self::is_encoded_to_keccak(&input.type_) {
+            Ok(Value::FixedBytes(bytes))
+        } else {
+            Value::decode_from_slice(&bytes, core::slice::from_ref(&input.type_))?
+                .first()
+                .ok_or_else(|| anyhow!("no value decoded from topics entry"))
+                .cloned()
+        }
+    }
+
+    /// Encode event params into a log's topics and data.
+    ///
+    /// Values must be given in the same order as the event inputs, along with their
+    /// param names. Indexed values are encoded as topics, hashing the ones of dynamic
+    /// or composite types, and the remaining values are encoded as the log data. The
+    /// event topic hash is included as the first topic unless the event is anonymous.
+    pub fn encode_log(&self, values: &[(String, Value)]) -> Result<(Vec<H256>, Vec<u8>)> {
+        if values.len() != self.inputs.len() {
+            return Err(anyhow!(
+                "expected {} event values, got {}",
+                self.inputs.len(),
+                values.len()
+            ));
+        }
+
+        let mut topics = vec![];
+        if !self.anonymous {
+            topics.push(self.topic());
+        }
+
+        let mut data_values = vec![];
+
+        for (input, (name, value)) in self.inputs.iter().zip(values) {
+            if input.name != *name {
+                return Err(anyhow!(
+                    "expected value for event param {}, got {}",
+                    input.name,
+                    name
+                ));
+            }
+
+            if value.type_of() != input.type_ {
+                return Err(anyhow!(
+                    "expected {} value for event param {}, got {}",
+                    input.type_,
+                    input.name,
+                    value.type_of()
+                ));
+            }
+
+            if input.indexed.unwrap_or(false) {
+                topics.push(Self::encode_topic(value));
+            } else {
+                data_values.push(value.clone());
+            }
+        }
+
+        Ok((topics, Value::encode(&data_values)))
+    }
+
+    /// Builds the topics used to filter this event's logs.
+    ///
+    /// `indexed` holds the values to match for the event's indexed params, in order,
+    /// with `None` matching any value. Params past the end of `indexed` match any value
+    /// as well. The event topic hash is included first unless the event is anonymous.
+    ///
+    /// Like in [`Event::encode_log`], values of dynamic or composite types are matched
+    /// by the Keccak-256 hash Solidity stores in their topic, e.g. an indexed
+    /// `uint256[]` by the hash of its elements' words, without offset or length.
+    pub fn filter_topics(&self, indexed: &[Option<Value>]) -> Result<Vec<Option<H256>>> {
+        let indexed_inputs: Vec<_> = self.indexed_inputs().collect();
+
+        if indexed.len() > indexed_inputs.len() {
+            return Err(anyhow!(
+                "expected at most {} indexed values, got {}",
+                indexed_inputs.len(),
+                indexed.len()
+            ));
+        }
+
+        let mut topics = vec![];
+        if !self.anonymous {
+            topics.push(Some(self.topic()));
+        }
+
+        for (input, value) in indexed_inputs.into_iter().zip(indexed) {
+            let topic = match value {
+                Some(value) if value.type_of() != input.type_ => {
+                    return Err(anyhow!(
+                        "expected {} value for event param {}, got {}",
+                        input.type_,
+                        input.name,
+                        value.type_of()
+                    ))
+                }
+                Some(value) => Some(Self::encode_topic(value)),
+                None => None,
+            };
+
+            topics.push(topic);
+        }
+
+        Ok(topics)
+    }
+
+    // Encodes an indexed param value as a log topic.
+    fn encode_topic(value: &Value) -> H256 {
+        if Self::is_encoded_to_keccak(&value.type_of()) {
+            let mut preimage = vec![];
+            Self::encode_topic_preimage(value, &mut preimage);
+
+            H256::from(TinyKeccak::keccak256(&preimage))
+        } else {
+            H256::from_slice(&Value::encode(core::slice::from_ref(value)))
+        }
+    }
+
+    // Writes the in-place encoding hashed for indexed params of dynamic or composite
+    // types: bytes and strings are not padded at the top level and arrays and tuples
+    // are the concatenation of their elements' in-place encodings, padded to 32 bytes
+    // and without any length prefix.
+    fn encode_topic_preimage(value: &Value, buf: &mut Vec<u8>) {
+        match value {
+            Value::String(s) => buf.extend_from_slice(s.as_bytes()),
+
+            Value::Bytes(bytes) => buf.extend_from_slice(bytes),
+
+            Value::FixedArray(values, _) | Value::Array(values, _) => {
+                for value in values {
+                    Self::encode_topic_element(value, buf);
+                }
+            }
+
+            Value::Tuple(values) => {
+                for (_, value) in values {
+                    Self::encode_topic_element(value, buf);
+                }
+            }
+
+            _ => buf.extend(Value::encode(core::slice::from_ref(value))),
+        }
+    }
+
+    fn encode_topic_element(value: &Value, buf: &mut Vec<u8>) {
+        let start = buf.len();
+        Self::encode_topic_preimage(value, buf);
+
+        let len = buf.len() - start;
+        let r = len % 32;
+        if r != 0 {
+            buf.resize(buf.len() + 32 - r, 0);
+        }
+    }
+
     fn is_encoded_to_keccak(ty: &Type) -> bool {
         matches!(
             ty,
@@ -122,26 +412,41 @@ mod test {
 
     use super::*;
 
-    use ethereum_types::U256;
+    use ethereum_types::{H160, U256};
     use pretty_assertions::assert_eq;
 
     fn test_event() -> Event {
-        Event {
-            name: "Approve".to_string(),
-            inputs: vec![
-                Param {
-                    name: "x".to_string(),
-                    type_: Type::Uint(56),
-                    indexed: Some(true),
-                },
-                Param {
-                    name: "y".to_string(),
-                    type_: Type::String,
-                    indexed: Some(true),
-                },
-            ],
-            anonymous: false,
-        }
+        Event::new("Approve")
+            .input("x", Type::Uint(56), true)
+            .input("y", Type::String, true)
+    }
+
+    #[test]
+    fn test_builder() {
+        let evt = Event::new("E")
+            .input("x", Type::Address, true)
+            .input("y", Type::Uint(256), false)
+            .anonymous(true);
+
+        assert_eq!(
+            evt,
+            Event {
+                name: "E".to_string(),
+                inputs: vec![
+                    Param {
+                        name: "x".to_string(),
+                        type_: Type::Address,
+                        indexed: Some(true),
+                    },
+                    Param {
+                        name: "y".to_string(),
+                        type_: Type::Uint(256),
+                        indexed: Some(false),
+                    },
+                ],
+                anonymous: true,
+            }
+        );
     }
 
     #[test]
@@ -162,6 +467,64 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_topic_ignores_indexed() {
+        let evt = test_event();
+
+        let mut unindexed = evt.clone();
+        for input in &mut unindexed.inputs {
+            input.indexed = Some(false);
+        }
+
+        assert_eq!(unindexed.signature(), evt.signature());
+        assert_eq!(unindexed.topic(), evt.topic());
+    }
+
+    #[test]
+    fn test_anonymous_signature_and_topic() {
+        let evt = test_event();
+        let anon_evt = evt.clone().anonymous(true);
+
+        assert_eq!(anon_evt.signature(), "Approve(uint56,string)");
+        assert_eq!(anon_evt.topic(), evt.topic());
+    }
+
+    #[test]
+    fn test_indexed_inputs() {
+        let evt = Event::new("E")
+            .input("a", Type::Address, true)
+            .input("b", Type::Uint(256), false)
+            .input("c", Type::Bool, true)
+            .input("d", Type::String, false);
+
+        let names = |inputs: Vec<&Param>| inputs.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+
+        assert_eq!(names(evt.indexed_inputs().collect()), vec!["a", "c"]);
+        assert_eq!(names(evt.non_indexed_inputs().collect()), vec!["b", "d"]);
+    }
+
+    #[test]
+    fn test_error_selector() {
+        let err = Error {
+            name: "InsufficientBalance".to_string(),
+            inputs: vec![
+                Param {
+                    name: "available".to_string(),
+                    type_: Type::Uint(256),
+                    indexed: None,
+                },
+                Param {
+                    name: "required".to_string(),
+                    type_: Type::Uint(256),
+                    indexed: None,
+                },
+            ],
+        };
+
+        assert_eq!(err.signature(), "InsufficientBalance(uint256,uint256)");
+        assert_eq!(err.selector(), [0xcf, 0x47, 0x91, 0x81]);
+    }
+
     #[test]
     fn test_decode_data_from_slice() {
         let topics: Vec<_> = [
@@ -212,8 +575,8 @@ mod test {
             functions: vec![],
             events: vec![evt],
             errors: vec![],
-            has_receive: false,
-            has_fallback: false,
+            receive: None,
+            fallback: None,
         };
 
         assert_eq!(
@@ -230,5 +593,446 @@ mod test {
                 ])
             )
         );
+
+        let (_, decoded) = abi.decode_log_from_slice(&topics, &data).unwrap();
+
+        assert_eq!(
+            abi.events[0]
+                .decode_data_from_hex(&topics, &format!("0x{}", hex::encode(&data)))
+                .expect("decode_data_from_hex failed"),
+            decoded
+        );
+        assert_eq!(
+            abi.events[0]
+                .decode_data_from_hex(&topics, &hex::encode(&data))
+                .expect("decode_data_from_hex failed"),
+            decoded
+        );
+        assert!(abi.events[0].decode_data_from_hex(&topics, "0x0").is_err());
+
+        let values: Vec<_> = decoded
+            .iter()
+            .map(|decoded_param| {
+                (
+                    decoded_param.param.name.clone(),
+                    decoded_param.value.clone(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            abi.events[0]
+                .encode_log(&values)
+                .expect("encode_log failed"),
+            (topics, data)
+        );
+    }
+
+    #[test]
+    fn test_decode_topics() {
+        let evt = Event::new("Transfer")
+            .input("from", Type::Address, true)
+            .input("to", Type::Address, true)
+            .input("value", Type::Uint(256), false)
+            .input("memo", Type::String, true);
+
+        let from = H160::repeat_byte(0x11);
+        let to = H160::repeat_byte(0x22);
+        let (topics, _) = evt
+            .encode_log(&[
+                ("from".to_string(), Value::Address(from)),
+                ("to".to_string(), Value::Address(to)),
+                ("value".to_string(), Value::Uint(U256::from(5), 256)),
+                ("memo".to_string(), Value::String("abc".to_string())),
+            ])
+            .expect("encode_log failed");
+
+        let decoded = evt.decode_topics(&topics).expect("decode_topics failed");
+        assert_eq!(
+            decoded,
+            vec![
+                (evt.inputs[0].clone(), Value::Address(from)),
+                (evt.inputs[1].clone(), Value::Address(to)),
+                (
+                    evt.inputs[3].clone(),
+                    Value::FixedBytes(TinyKeccak::keccak256(b"abc").to_vec())
+                ),
+            ]
+        );
+
+        let anon_evt = evt.clone().anonymous(true);
+        assert_eq!(
+            anon_evt
+                .decode_topics(&topics[1..])
+                .expect("decode_topics failed"),
+            decoded
+        );
+
+        let err = evt
+            .decode_topics(&topics[1..])
+            .expect_err("decode_topics succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::TopicCountMismatch {
+                expected: 4,
+                got: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_static_indexed_params() {
+        let evt = Event::new("E")
+            .input("a", Type::FixedBytes(32), true)
+            .input("b", Type::Address, true)
+            .input("c", Type::Bool, true);
+
+        let addr = H160::repeat_byte(0x11);
+
+        let topics = vec![
+            evt.topic(),
+            H256::repeat_byte(0xab),
+            H256::from(addr),
+            H256::from_low_u64_be(1),
+        ];
+
+        let decoded = evt
+            .decode_data_from_slice(&topics, &[])
+            .expect("decode_data_from_slice failed");
+
+        assert_eq!(
+            decoded.values().cloned().collect::<Vec<_>>(),
+            vec![
+                Value::FixedBytes(vec![0xab; 32]),
+                Value::Address(addr),
+                Value::Bool(true),
+            ]
+        );
+
+        let evt = Event::new("E").input("a", Type::FixedBytes(4), true);
+        let mut topic = [0u8; 32];
+        topic[0..4].copy_from_slice(&[1, 2, 3, 4]);
+
+        let decoded = evt
+            .decode_data_from_slice(&[evt.topic(), H256::from(topic)], &[])
+            .expect("decode_data_from_slice failed");
+
+        assert_eq!(decoded[0].value, Value::FixedBytes(vec![1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_decode_overloaded_events() {
+        let abi = Abi {
+            constructor: None,
+            functions: vec![],
+            events: vec![
+                Event::new("Transfer")
+                    .input("from", Type::Address, true)
+                    .input("value", Type::Uint(256), false),
+                Event::new("Transfer")
+                    .input("from", Type::Address, true)
+                    .input("value", Type::Uint(128), false)
+                    .input("memo", Type::String, false),
+            ],
+            errors: vec![],
+            receive: None,
+            fallback: None,
+        };
+
+        assert_eq!(
+            abi.events_by_name("Transfer"),
+            vec![&abi.events[0], &abi.events[1]]
+        );
+        assert!(abi.events_by_name("Approval").is_empty());
+
+        let from = H256::from(H160::repeat_byte(0x11));
+
+        let data = Value::encode(&[Value::Uint(U256::from(5), 256)]);
+        let (evt, decoded) = abi
+            .decode_log_from_slice(&[abi.events[0].topic(), from], &data)
+            .expect("decode_log_from_slice failed");
+        assert_eq!(evt, &abi.events[0]);
+        assert_eq!(decoded[1].value, Value::Uint(U256::from(5), 256));
+
+        let data = Value::encode(&[
+            Value::Uint(U256::from(5), 128),
+            Value::String("memo".to_string()),
+        ]);
+        let (evt, decoded) = abi
+            .decode_log_from_slice(&[abi.events[1].topic(), from], &data)
+            .expect("decode_log_from_slice failed");
+        assert_eq!(evt, &abi.events[1]);
+        assert_eq!(decoded[2].value, Value::String("memo".to_string()));
+    }
+
+    #[test]
+    fn test_encode_log() {
+        let evt = test_event();
+
+        let (topics, data) = evt
+            .encode_log(&[
+                ("x".to_string(), Value::Uint(U256::from(10), 56)),
+                ("y".to_string(), Value::String("abc".to_string())),
+            ])
+            .expect("encode_log failed");
+
+        assert_eq!(
+            topics,
+            vec![
+                evt.topic(),
+                H256::from_low_u64_be(10),
+                // keccak256("abc")
+                H256::from_str("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45")
+                    .unwrap(),
+            ]
+        );
+        assert!(data.is_empty());
+
+        assert!(evt
+            .encode_log(&[("x".to_string(), Value::Uint(U256::from(10), 56))])
+            .is_err());
+        assert!(evt
+            .encode_log(&[
+                ("y".to_string(), Value::String("abc".to_string())),
+                ("x".to_string(), Value::Uint(U256::from(10), 56)),
+            ])
+            .is_err());
+        assert!(evt
+            .encode_log(&[
+                ("x".to_string(), Value::Uint(U256::from(10), 256)),
+                ("y".to_string(), Value::String("abc".to_string())),
+            ])
+            .is_err());
+    }
+
+    #[test]
+    fn test_decode_log_topic_count_mismatch() {
+        let topic = H256::from_low_u64_be(1);
+
+        let evt =
+            Event::new("E")
+                .input("x", Type::Uint(256), true)
+                .input("y", Type::Uint(256), false);
+
+        let data = [0u8; 32];
+
+        assert!(evt.decode_log(&[evt.topic(), topic], &data, true).is_ok());
+        assert!(evt.decode_log(&[topic], &data, false).is_ok());
+
+        let err = evt
+            .decode_data_from_slice(&[evt.topic()], &data)
+            .expect_err("decode succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::TopicCountMismatch {
+                expected: 2,
+                got: 1
+            })
+        );
+
+        let err = evt
+            .decode_log(&[evt.topic(), topic, topic], &data, true)
+            .expect_err("decode succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::TopicCountMismatch {
+                expected: 2,
+                got: 3
+            })
+        );
+
+        let anon_evt = evt.anonymous(true);
+
+        assert!(anon_evt.decode_data_from_slice(&[topic], &data).is_ok());
+
+        let err = anon_evt
+            .decode_data_from_slice(&[anon_evt.topic(), topic], &data)
+            .expect_err("decode succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::TopicCountMismatch {
+                expected: 1,
+                got: 2
+            })
+        );
+
+        assert!(anon_evt
+            .decode_log(&[anon_evt.topic(), topic], &data, true)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_decode_anonymous_log_with_topic0() {
+        let evt = Event::new("Transfer")
+            .input("from", Type::Address, true)
+            .input("to", Type::Address, true)
+            .input("amount", Type::Uint(256), true)
+            .anonymous(true);
+
+        let from = H256::from_low_u64_be(0xaa);
+        let to = H256::from_low_u64_be(0xbb);
+        let amount = H256::from_low_u64_be(100);
+
+        let decoded = evt
+            .decode_data_from_slice(&[from, to, amount], &[])
+            .expect("decode_data_from_slice failed");
+        assert_eq!(
+            decoded.get("amount"),
+            Some(&Value::Uint(U256::from(100), 256))
+        );
+
+        // passing topic0 as well would shift every indexed value by one
+        let err = evt
+            .decode_data_from_slice(&[evt.topic(), from, to, amount], &[])
+            .expect_err("decode succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::TopicCountMismatch {
+                expected: 3,
+                got: 4
+            })
+        );
+        assert_eq!(err.to_string(), "expected 3 log topics, got 4");
+    }
+
+    #[test]
+    fn test_filter_topics() {
+        let evt = Event::new("Transfer")
+            .input("from", Type::Address, true)
+            .input("to", Type::Address, true)
+            .input("amount", Type::Uint(256), false);
+
+        let from = H160::from_low_u64_be(0xaa);
+
+        assert_eq!(
+            evt.filter_topics(&[]).expect("filter_topics failed"),
+            vec![Some(evt.topic())]
+        );
+        assert_eq!(
+            evt.filter_topics(&[None, Some(Value::Address(from))])
+                .expect("filter_topics failed"),
+            vec![Some(evt.topic()), None, Some(H256::from(from))]
+        );
+
+        assert!(evt
+            .filter_topics(&[None, None, Some(Value::Uint(U256::one(), 256))])
+            .is_err());
+        assert!(evt
+            .filter_topics(&[Some(Value::Uint(U256::one(), 256))])
+            .is_err());
+
+        let evt = evt.anonymous(true);
+        assert_eq!(
+            evt.filter_topics(&[Some(Value::Address(from))])
+                .expect("filter_topics failed"),
+            vec![Some(H256::from(from))]
+        );
+    }
+
+    #[test]
+    fn test_indexed_array_topics() {
+        let evt = Event::new("E")
+            .input("ids", Type::Array(Box::new(Type::Uint(256))), true)
+            .input("names", Type::Array(Box::new(Type::String)), true);
+
+        let ids = Value::Array(
+            (1..=3).map(|i| Value::Uint(U256::from(i), 256)).collect(),
+            Type::Uint(256),
+        );
+        let names = Value::Array(
+            vec![
+                Value::String("ab".to_string()),
+                Value::String("c".to_string()),
+            ],
+            Type::String,
+        );
+
+        // keccak256(abi.encodePacked(ids)), i.e. the hash of the elements' words
+        let mut ids_preimage = [0u8; 96];
+        ids_preimage[31] = 1;
+        ids_preimage[63] = 2;
+        ids_preimage[95] = 3;
+        let ids_topic = H256::from(TinyKeccak::keccak256(&ids_preimage));
+
+        // each string element is padded to 32 bytes, without a length prefix
+        let mut names_preimage = [0u8; 64];
+        names_preimage[..2].copy_from_slice(b"ab");
+        names_preimage[32] = b'c';
+        let names_topic = H256::from(TinyKeccak::keccak256(&names_preimage));
+
+        let (topics, data) = evt
+            .encode_log(&[
+                ("ids".to_string(), ids.clone()),
+                ("names".to_string(), names.clone()),
+            ])
+            .expect("encode_log failed");
+        assert_eq!(topics, vec![evt.topic(), ids_topic, names_topic]);
+        assert!(data.is_empty());
+
+        assert_eq!(
+            evt.filter_topics(&[Some(ids), Some(names)])
+                .expect("filter_topics failed"),
+            vec![Some(evt.topic()), Some(ids_topic), Some(names_topic)]
+        );
+    }
+
+    #[test]
+    fn test_indexed_tuple_param() {
+        let abi: Abi = serde_json::from_str(
+            r#"[{
+                "type": "event",
+                "name": "E",
+                "inputs": [
+                    {
+                        "name": "s",
+                        "type": "tuple",
+                        "indexed": true,
+                        "components": [
+                            {"name": "a", "type": "uint256"},
+                            {"name": "b", "type": "string"}
+                        ]
+                    },
+                    {"name": "x", "type": "uint256", "indexed": false}
+                ],
+                "anonymous": false
+            }]"#,
+        )
+        .expect("failed to parse ABI");
+        let evt = &abi.events[0];
+
+        assert_eq!(evt.signature(), "E((uint256,string),uint256)");
+
+        let s = Value::Tuple(vec![
+            ("a".to_string(), Value::Uint(U256::from(1), 256)),
+            ("b".to_string(), Value::String("abc".to_string())),
+        ]);
+
+        // Struct members are encoded in place and padded, with no offsets or lengths.
+        let mut preimage = [0u8; 64];
+        preimage[31] = 1;
+        preimage[32..35].copy_from_slice(b"abc");
+        let topic = H256::from(TinyKeccak::keccak256(&preimage));
+
+        let (topics, data) = evt
+            .encode_log(&[
+                ("s".to_string(), s.clone()),
+                ("x".to_string(), Value::Uint(U256::from(7), 256)),
+            ])
+            .expect("encode_log failed");
+        assert_eq!(topics, vec![evt.topic(), topic]);
+
+        assert_eq!(
+            evt.filter_topics(&[Some(s)]).expect("filter_topics failed"),
+            vec![Some(evt.topic()), Some(topic)]
+        );
+
+        let (_, decoded) = abi
+            .decode_log_from_slice(&topics, &data)
+            .expect("decode_log_from_slice failed");
+        assert_eq!(
+            decoded.get("s"),
+            Some(&Value::FixedBytes(topic.as_bytes().to_vec()))
+        );
+        assert_eq!(decoded.get("x"), Some(&Value::Uint(U256::from(7), 256)));
     }
 }
@@ -0,0 +1,513 @@
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
+use anyhow::{anyhow, Result};
+
+use crate::{
+    Abi, AbiError, Constructor, Error, Event, Function, Param, StateMutability, Type,
+    DEFAULT_MAX_DEPTH,
+};
+
+// Parses an ABI from human readable Solidity declarations, one per line.
+pub(crate) fn parse(lines: &[&str]) -> Result<Abi> {
+    let mut abi = Abi {
+        constructor: None,
+        functions: vec![],
+        events: vec![],
+        errors: vec![],
+        receive: None,
+        fallback: None,
+    };
+
+    for (i, line) in lines.iter().enumerate() {
+        parse_line(&mut abi, line).map_err(|err| {
+            // errors callers may want to handle programmatically are kept as they are
+            if err.downcast_ref::<AbiError>().is_some() {
+                err
+            } else {
+                anyhow!("invalid human readable ABI at line {}: {}", i + 1, err)
+            }
+        })?;
+    }
+
+    Ok(abi)
+}
+
+fn parse_line(abi: &mut Abi, line: &str) -> Result<()> {
+    let line = line.trim();
+    let line = line.strip_suffix(';').unwrap_or(line).trim_end();
+
+    let (keyword, rest) = line
+        .split_once(|c: char| c.is_whitespace() || c == '(')
+        .map(|(keyword, _)| (keyword, line[keyword.len()..].trim_start()))
+        .unwrap_or((line, ""));
+
+    match keyword {
+        "function" => {
+            let (name, rest) = split_name(rest)?;
+            let (inputs, rest) = parse_params(rest, false)?;
+
+            let (modifiers, outputs) = match find_word(rest, "returns") {
+                Some(i) => {
+                    let (outputs, tail) = parse_params(rest[i + "returns".len()..].trim(), false)?;
+                    expect_end(tail)?;
+
+                    (&rest[..i], outputs)
+                }
+                None => (rest, vec![]),
+            };
+
+            abi.functions.push(Function {
+                name: name.to_string(),
+                inputs,
+                outputs,
+                state_mutability: parse_mutability(modifiers, StateMutability::NonPayable)?,
+            });
+        }
+
+        "event" => {
+            let (name, rest) = split_name(rest)?;
+            let (inputs, rest) = parse_params(rest, true)?;
+
+            let anonymous = match rest.trim() {
+                "" => false,
+                "anonymous" => true,
+                rest => return Err(anyhow!("unexpected input after event params: {}", rest)),
+            };
+
+            abi.events.push(Event {
+                name: name.to_string(),
+                inputs,
+                anonymous,
+            });
+        }
+
+        "error" => {
+            let (name, rest) = split_name(rest)?;
+            let (inputs, rest) = parse_params(rest, false)?;
+            expect_end(rest)?;
+
+            abi.errors.push(Error {
+                name: name.to_string(),
+                inputs,
+            });
+        }
+
+        "constructor" => {
+            let (inputs, rest) = parse_params(rest, false)?;
+
+            if abi.constructor.is_some() {
+                return Err(anyhow!("duplicate constructor"));
+            }
+
+            abi.constructor = Some(Constructor {
+                inputs,
+                state_mutability: parse_mutability(rest, StateMutability::NonPayable)?,
+            });
+        }
+
+        "receive" => {
+            let (inputs, rest) = parse_params(rest, false)?;
+            if !inputs.is_empty() {
+                return Err(anyhow!("receive takes no params"));
+            }
+
+            // receive functions are always payable
+            abi.receive = Some(parse_mutability(rest, StateMutability::Payable)?);
+        }
+
+        "fallback" => {
+            let (inputs, rest) = parse_params(rest, false)?;
+            if !inputs.is_empty() {
+                return Err(anyhow!("fallback takes no params"));
+            }
+
+            abi.fallback = Some(parse_mutability(rest, StateMutability::NonPayable)?);
+        }
+
+        _ => return Err(anyhow!("unknown declaration: {}", line)),
+    }
+
+    Ok(())
+}
+
+// Splits a declaration name from the rest of the declaration.
+fn split_name(input: &str) -> Result<(&str, &str)> {
+    let end = input.find('(').unwrap_or(input.len());
+    let name = input[..end].trim();
+
+    if !is_identifier(name) {
+        return Err(anyhow!("invalid name: {}", name));
+    }
+
+    Ok((name, &input[end..]))
+}
+
+// Parses a parenthesized param list, returning the params and the remaining
+// input.
+fn parse_params(input: &str, allow_indexed: bool) -> Result<(Vec<Param>, &str)> {
+    let input = input.trim_start();
+    if !input.starts_with('(') {
+        return Err(anyhow!("expected params list at: {}", input));
+    }
+
+    let end = closing_paren(input)?;
+
+    let params = split_top_level(&input[1..end])
+        .into_iter()
+        .map(|param| parse_param(param, allow_indexed, DEFAULT_MAX_DEPTH))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((params, &input[(end + 1)..]))
+}
+
+// Parses a single param, e.g. `address indexed from` or `(uint256 a, bool b)[] s`.
+fn parse_param(input: &str, allow_indexed: bool, max_depth: usize) -> Result<Param> {
+    let input = input.trim();
+
+    let (type_, rest) = parse_param_type(input, max_depth)?;
+
+    let mut indexed = None;
+    let mut name = None;
+
+    for word in rest.split_whitespace() {
+        match word {
+            "indexed" if allow_indexed => indexed = Some(true),
+            "indexed" => return Err(anyhow!("only event params can be indexed")),
+            "memory" | "calldata" | "storage" => {}
+            word if name.is_none() && is_identifier(word) => name = Some(word),
+            word => return Err(anyhow!("unexpected word in param {}: {}", input, word)),
+        }
+    }
+
+    Ok(Param {
+        name: name.unwrap_or_default().to_string(),
+        type_,
+        indexed: if allow_indexed {
+            Some(indexed.unwrap_or(false))
+        } else {
+            None
+        },
+    })
+}
+
+// Parses the type at the start of a param, returning it along with the rest of
+// the param.
+fn parse_param_type(input: &str, max_depth: usize) -> Result<(Type, &str)> {
+    let tuple_input = input.strip_prefix("tuple").unwrap_or(input);
+
+    if !tuple_input.starts_with('(') {
+        let end = input.find(char::is_whitespace).unwrap_or(input.len());
+        let type_ = input[..end].parse()?;
+
+        return Ok((type_, &input[end..]));
+    }
+
+    if max_depth == 0 {
+        return Err(AbiError::MaxDepthExceeded {
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+        .into());
+    }
+
+    let end = closing_paren(tuple_input)?;
+
+    let components = split_top_level(&tuple_input[1..end])
+        .into_iter()
+        .map(|component| {
+            parse_param(component, false, max_depth - 1).map(|param| (param.name, param.type_))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut type_ = Type::Tuple(components);
+    let mut rest = &tuple_input[(end + 1)..];
+
+    while let Some(suffix) = rest.strip_prefix('[') {
+        let end = suffix
+            .find(']')
+            .ok_or_else(|| anyhow!("unclosed array brackets in: {}", input))?;
+
+        type_ = match &suffix[..end] {
+            "" => Type::Array(Box::new(type_)),
+            size => Type::FixedArray(
+                Box::new(type_),
+                size.parse()
+                    .map_err(|_| anyhow!("invalid array size: {}", size))?,
+            ),
+        };
+        rest = &suffix[(end + 1)..];
+    }
+
+    if type_.depth() > DEFAULT_MAX_DEPTH {
+        return Err(AbiError::MaxDepthExceeded {
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+        .into());
+    }
+
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return Err(anyhow!("invalid type: {}", input));
+    }
+
+    Ok((type_, rest))
+}
+
+// Parses function modifiers into a state mutability kind.
+fn parse_mutability(input: &str, default: StateMutability) -> Result<StateMutability> {
+    let mut state_mutability = default;
+
+    for word in input.split_whitespace() {
+        state_mutability = match word {
+            "external" | "public" | "virtual" | "override" => continue,
+            "pure" => StateMutability::Pure,
+            "view" | "constant" => StateMutability::View,
+            "payable" => StateMutability::Payable,
+            "nonpayable" => StateMutability::NonPayable,
+            word => return Err(anyhow!("unexpected modifier: {}", word)),
+        };
+    }
+
+    Ok(state_mutability)
+}
+
+// Returns the position of the parenthesis closing the one the input starts with.
+fn closing_paren(input: &str) -> Result<usize> {
+    let mut depth = 0usize;
+
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(i);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Err(anyhow!("unbalanced parentheses in: {}", input))
+}
+
+// Splits a comma separated list on its top level commas only.
+fn split_top_level(input: &str) -> Vec<&str> {
+    if input.trim().is_empty() {
+        return vec![];
+    }
+
+    let mut items = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&input[start..]);
+
+    items
+}
+
+// Finds a whole word in the input.
+fn find_word(input: &str, word: &str) -> Option<usize> {
+    input.match_indices(word).map(|(i, _)| i).find(|&i| {
+        let before = input[..i].chars().next_back();
+        let after = input[(i + word.len())..].chars().next();
+
+        !before.is_some_and(is_identifier_char) && !after.is_some_and(is_identifier_char)
+    })
+}
+
+fn expect_end(input: &str) -> Result<()> {
+    match input.trim() {
+        "" => Ok(()),
+        rest => Err(anyhow!("unexpected input: {}", rest)),
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    !s.is_empty()
+        && !s.starts_with(|c: char| c.is_ascii_digit())
+        && s.chars().all(is_identifier_char)
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn functions() {
+        let abi = parse(&[
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function balanceOf(address owner) external view returns (uint256 balance);",
+            "function deposit() payable",
+            "function name() pure returns (string memory)",
+        ])
+        .expect("parse failed");
+
+        assert_eq!(
+            abi.functions,
+            vec![
+                Function::new("transfer")
+                    .input("to", Type::Address)
+                    .input("amount", Type::Uint(256))
+                    .output("", Type::Bool),
+                Function::new("balanceOf")
+                    .input("owner", Type::Address)
+                    .output("balance", Type::Uint(256))
+                    .mutability(StateMutability::View),
+                Function::new("deposit").mutability(StateMutability::Payable),
+                Function::new("name")
+                    .output("", Type::String)
+                    .mutability(StateMutability::Pure),
+            ]
+        );
+        assert_eq!(abi.functions[0].method_id(), [0xa9, 0x05, 0x9c, 0xbb]);
+    }
+
+    #[test]
+    fn events_and_errors() {
+        let abi = parse(&[
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "event Log(string) anonymous",
+            "error InsufficientBalance(uint256 available, uint256 required)",
+        ])
+        .expect("parse failed");
+
+        assert_eq!(
+            abi.events,
+            vec![
+                Event::new("Transfer")
+                    .input("from", Type::Address, true)
+                    .input("to", Type::Address, true)
+                    .input("value", Type::Uint(256), false),
+                Event::new("Log")
+                    .input("", Type::String, false)
+                    .anonymous(true),
+            ]
+        );
+        assert_eq!(
+            abi.events[0].topic(),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+                .parse()
+                .unwrap()
+        );
+
+        assert_eq!(
+            abi.errors,
+            vec![Error {
+                name: "InsufficientBalance".to_string(),
+                inputs: vec![
+                    Param {
+                        name: "available".to_string(),
+                        type_: Type::Uint(256),
+                        indexed: None,
+                    },
+                    Param {
+                        name: "required".to_string(),
+                        type_: Type::Uint(256),
+                        indexed: None,
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn constructor_receive_and_fallback() {
+        let abi = parse(&[
+            "constructor(string name, uint8 decimals) payable",
+            "receive() external payable",
+            "fallback() external",
+        ])
+        .expect("parse failed");
+
+        assert_eq!(
+            abi.constructor,
+            Some(Constructor {
+                inputs: vec![
+                    Param {
+                        name: "name".to_string(),
+                        type_: Type::String,
+                        indexed: None,
+                    },
+                    Param {
+                        name: "decimals".to_string(),
+                        type_: Type::Uint(8),
+                        indexed: None,
+                    },
+                ],
+                state_mutability: StateMutability::Payable,
+            })
+        );
+        assert_eq!(abi.receive, Some(StateMutability::Payable));
+        assert_eq!(abi.fallback, Some(StateMutability::NonPayable));
+    }
+
+    #[test]
+    fn tuples_and_arrays() {
+        let abi = parse(&[
+            "function swap((address tokenIn, uint24 fee)[] calldata paths, tuple(bool, bytes32)[2] flags, uint256[][3] amounts)",
+        ])
+        .expect("parse failed");
+
+        let f = &abi.functions[0];
+        assert_eq!(
+            f.signature(),
+            "swap((address,uint24)[],(bool,bytes32)[2],uint256[][3])"
+        );
+        assert_eq!(
+            f.inputs[0].type_,
+            Type::Array(Box::new(Type::Tuple(vec![
+                ("tokenIn".to_string(), Type::Address),
+                ("fee".to_string(), Type::Uint(24)),
+            ])))
+        );
+        assert_eq!(f.inputs[0].name, "paths");
+        assert_eq!(f.inputs[1].name, "flags");
+    }
+
+    #[test]
+    fn invalid_declarations() {
+        for line in [
+            "struct S(uint256 a)",
+            "function (uint256)",
+            "function f(uint7)",
+            "function f(uint256 a b)",
+            "function f(uint256 indexed a)",
+            "function f(uint256 indexed)",
+            "function f(uint256) sometimes",
+            "function f((uint256, bool)",
+            "event E(uint256) anonymous indexed",
+            "receive(uint256) payable",
+        ] {
+            assert!(parse(&[line]).is_err(), "parsed {}", line);
+        }
+
+        let err = parse(&["function f()", "function g(foo)"]).expect_err("parse succeeded");
+        assert!(err
+            .to_string()
+            .starts_with("invalid human readable ABI at line 2"));
+
+        let nested = (0..40).fold(String::from("uint256"), |ty, _| format!("({})", ty));
+        let err = parse(&[&format!("function f({})", nested)]).expect_err("parse succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::MaxDepthExceeded {
+                max_depth: DEFAULT_MAX_DEPTH
+            })
+        );
+    }
+}
@@ -0,0 +1,62 @@
+/// Keccak-256 hash function used for computing function selectors and event topics.
+///
+/// Implement this trait to plug in a different Keccak-256 implementation, e.g. a
+/// hardware accelerated one.
+pub trait Keccak256 {
+    /// Computes the Keccak-256 hash of the given input.
+    fn keccak256(input: &[u8]) -> [u8; 32];
+}
+
+/// Default Keccak-256 implementation backed by the `tiny-keccak` crate.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TinyKeccak;
+
+impl Keccak256 for TinyKeccak {
+    fn keccak256(input: &[u8]) -> [u8; 32] {
+        use tiny_keccak::{Hasher, Keccak};
+
+        let mut keccak_out = [0u8; 32];
+        let mut hasher = Keccak::v256();
+        hasher.update(input);
+        hasher.finalize(&mut keccak_out);
+
+        keccak_out
+    }
+}
+
+// Computes the 4 bytes selector of the given signature.
+pub(crate) fn selector<H: Keccak256>(signature: &str) -> [u8; 4] {
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&H::keccak256(signature.as_bytes())[0..4]);
+
+    selector
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn tiny_keccak_empty_input() {
+        assert_eq!(
+            hex::encode(TinyKeccak::keccak256(&[])),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+    }
+
+    #[test]
+    fn custom_hasher() {
+        struct ZeroHasher;
+
+        impl Keccak256 for ZeroHasher {
+            fn keccak256(_: &[u8]) -> [u8; 32] {
+                [0u8; 32]
+            }
+        }
+
+        assert_eq!(selector::<ZeroHasher>("f()"), [0u8; 4]);
+        assert_eq!(selector::<TinyKeccak>("f()"), [0x26, 0x12, 0x1f, 0xf0]);
+    }
+}
@@ -1,13 +1,39 @@
 //! Ethereum Smart Contracts ABI (abstract binary interface) utility library.
+//!
+//! The `std` feature is enabled by default. Disabling it makes the crate usable in
+//! `no_std` environments, as long as an allocator is available. Building without
+//! `std` requires Rust 1.81 or later.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
 mod abi;
+#[cfg(any(test, feature = "proptest"))]
+pub mod arbitrary;
+mod convert;
+mod error;
+#[cfg(feature = "ethabi-compat")]
+pub mod ethabi_compat;
 mod event;
+mod human_readable;
+mod keccak;
 mod params;
+mod revert;
+pub mod typed_data;
 mod types;
+mod util;
 mod values;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub use abi::*;
+pub use convert::*;
+pub use error::*;
 pub use event::*;
+pub use keccak::*;
 pub use params::*;
+pub use revert::*;
 pub use types::*;
+pub use util::hex_to_bytes;
 pub use values::*;
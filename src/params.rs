@@ -1,7 +1,20 @@
+use alloc::{
+    boxed::Box,
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, rc::Rc};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
 
-use crate::{types::Type, Value};
+use crate::{
+    types::{Type, DEFAULT_MAX_DEPTH},
+    AbiError, Value,
+};
 
 /// ABI decoded param value.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -25,15 +38,45 @@ impl From<(Param, Value)> for DecodedParam {
 pub struct DecodedParams(Vec<DecodedParam>);
 
 impl DecodedParams {
+    /// Decodes values of the given params from bytes, keeping each param definition
+    /// along with its decoded value.
+    pub fn decode(params: &[Param], data: &[u8]) -> Result<DecodedParams> {
+        let tys: Vec<_> = params.iter().map(|param| param.type_.clone()).collect();
+
+        Ok(DecodedParams::from(
+            params
+                .iter()
+                .cloned()
+                .zip(Value::decode_from_slice(data, &tys)?)
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    /// Returns the value of the first param with the given name, if any.
+    ///
+    /// Values can also be accessed by position, e.g. `decoded[0].value`.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0
+            .iter()
+            .find(|decoded_param| decoded_param.param.name == name)
+            .map(|decoded_param| &decoded_param.value)
+    }
+
+    /// Returns an iterator over the decoded values in param order.
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.0.iter().map(|decoded_param| &decoded_param.value)
+    }
+
     /// Creates a reader.
     ///
     /// Parameters are indexed by name at reader creation.
-    pub fn reader(&self) -> DecodedParamsReader {
+    #[cfg(feature = "std")]
+    pub fn reader(&self) -> DecodedParamsReader<'_> {
         DecodedParamsReader::new(self)
     }
 }
 
-impl std::ops::Deref for DecodedParams {
+impl core::ops::Deref for DecodedParams {
     type Target = Vec<DecodedParam>;
 
     fn deref(&self) -> &Self::Target {
@@ -48,6 +91,7 @@ impl From<Vec<(Param, Value)>> for DecodedParams {
 }
 
 /// Provides fast read access to decoded params by parameter index and name.
+#[cfg(feature = "std")]
 pub struct DecodedParamsReader<'a> {
     /// Decoded params by parameter index.
     pub by_index: Vec<&'a DecodedParam>,
@@ -55,6 +99,7 @@ pub struct DecodedParamsReader<'a> {
     pub by_name: HashMap<&'a str, &'a DecodedParam>,
 }
 
+#[cfg(feature = "std")]
 impl<'a> DecodedParamsReader<'a> {
     fn new(decoded_params: &'a DecodedParams) -> Self {
         let by_index = decoded_params.iter().collect();
@@ -81,7 +126,37 @@ pub struct Param {
 }
 
 impl Param {
-    fn build_param_entry(&self) -> ParamEntry {
+    /// Returns whether the parameter's type is dynamic, i.e. encoded out of the head
+    /// region with an offset pointer.
+    pub fn is_dynamic(&self) -> bool {
+        self.type_.is_dynamic()
+    }
+
+    // Builds a param from its ABI JSON entry, rejecting types nested deeper than
+    // `max_depth`.
+    pub(crate) fn from_entry(entry: ParamEntry, max_depth: usize) -> Result<Param> {
+        let ty = match parse_exact_type(Rc::new(entry.components), &entry.type_, max_depth) {
+            Ok((_, ty)) if ty.depth() <= max_depth => ty,
+            Ok(_) | Err(nom::Err::Failure(TypeParseError::MaxDepthExceeded)) => {
+                return Err(AbiError::MaxDepthExceeded { max_depth }.into())
+            }
+            Err(_) if invalid_int_size(&entry.type_).is_some() => {
+                return Err(anyhow::anyhow!(
+                    "invalid type {}: int sizes must be multiples of 8 between 8 and 256",
+                    entry.type_
+                ))
+            }
+            Err(e) => return Err(anyhow::anyhow!(e.to_string())),
+        };
+
+        Ok(Param {
+            name: entry.name,
+            type_: ty,
+            indexed: entry.indexed,
+        })
+    }
+
+    pub(crate) fn build_param_entry(&self) -> ParamEntry {
         let tuple_params = match &self.type_ {
             Type::Tuple(params) => Some(params.clone()),
             Type::Array(ty) | Type::FixedArray(ty, _) => {
@@ -133,14 +208,29 @@ impl<'a> Deserialize<'a> for Param {
     {
         let entry: ParamEntry = Deserialize::deserialize(deserializer)?;
 
-        let (_, ty) = parse_exact_type(Rc::new(entry.components), &entry.type_)
-            .map_err(|e| serde::de::Error::custom(e.to_string()))?;
+        Param::from_entry(entry, DEFAULT_MAX_DEPTH)
+            .map_err(|e| serde::de::Error::custom(e.to_string()))
+    }
+}
 
-        Ok(Param {
-            name: entry.name.to_string(),
-            type_: ty,
-            indexed: entry.indexed,
-        })
+impl core::str::FromStr for Type {
+    type Err = anyhow::Error;
+
+    /// Parses a type from its canonical string form, e.g. `uint256[2]`.
+    ///
+    /// Tuple types can't be parsed from a string since their components are
+    /// described separately in ABI JSON.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_exact_type(Rc::new(None), s, DEFAULT_MAX_DEPTH) {
+            Ok((_, ty)) => Ok(ty),
+            Err(nom::Err::Failure(TypeParseError::MaxDepthExceeded)) => {
+                Err(AbiError::MaxDepthExceeded {
+                    max_depth: DEFAULT_MAX_DEPTH,
+                }
+                .into())
+            }
+            Err(_) => Err(anyhow::anyhow!("invalid type: {}", s)),
+        }
     }
 }
 
@@ -154,7 +244,7 @@ fn param_type_string(ty: &Type) -> String {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct ParamEntry {
+pub(crate) struct ParamEntry {
     pub name: String,
     #[serde(rename = "type")]
     pub type_: String,
@@ -164,12 +254,56 @@ struct ParamEntry {
     pub components: Option<Vec<ParamEntry>>,
 }
 
+impl ParamEntry {
+    // Rounds `int<M>`/`uint<M>` sizes that are not multiples of 8 up to the next
+    // multiple, in this entry and its components, recording each type replaced along
+    // with its replacement.
+    pub(crate) fn round_int_sizes(&mut self, fixups: &mut Vec<(String, String)>) {
+        if let Some((prefix, size, rest)) = invalid_int_size(&self.type_) {
+            let rounded = format!("{}{}{}", prefix, size + 8 - size % 8, rest);
+
+            fixups.push((
+                core::mem::replace(&mut self.type_, rounded.clone()),
+                rounded,
+            ));
+        }
+
+        for component in self.components.iter_mut().flatten() {
+            component.round_int_sizes(fixups);
+        }
+    }
+}
+
+// Splits an `int<M>`/`uint<M>` type string whose size is between 1 and 256 but not
+// a multiple of 8 into its prefix, size and array suffixes.
+fn invalid_int_size(type_: &str) -> Option<(&str, usize, &str)> {
+    let prefix = if type_.starts_with("uint") {
+        "uint"
+    } else if type_.starts_with("int") {
+        "int"
+    } else {
+        return None;
+    };
+
+    let rest = &type_[prefix.len()..];
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let size: usize = rest[..digits].parse().ok()?;
+
+    if size == 0 || size > 256 || size % 8 == 0 {
+        return None;
+    }
+
+    Some((prefix, size, &rest[digits..]))
+}
+
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{char, digit1},
     combinator::{all_consuming, map_res, opt, recognize, verify},
-    multi::many1,
+    multi::{many0, many1},
     sequence::delimited,
     IResult,
 };
@@ -177,6 +311,7 @@ use nom::{
 #[derive(Debug)]
 enum TypeParseError<I> {
     Error,
+    MaxDepthExceeded,
     NomError(nom::error::Error<I>),
 }
 
@@ -202,30 +337,36 @@ fn map_error<I, O>(res: IResult<I, O>) -> TypeParseResult<I, O> {
     res.map_err(|err| err.map(From::from))
 }
 
+// Parses a type, failing with `TypeParseError::MaxDepthExceeded` as soon as
+// arrays or tuples are found to nest deeper than `max_depth`.
 fn parse_exact_type(
     components: Rc<Option<Vec<ParamEntry>>>,
     input: &str,
+    max_depth: usize,
 ) -> TypeParseResult<&str, Type> {
-    all_consuming(parse_type(components))(input)
+    all_consuming(parse_type(components, max_depth))(input)
 }
 
 fn parse_type(
     components: Rc<Option<Vec<ParamEntry>>>,
+    max_depth: usize,
 ) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
     move |input: &str| {
-        alt((
-            parse_array(components.clone()),
-            parse_simple_type(components.clone()),
-        ))(input)
+        // the element type is parsed only once, as backtracking would reparse nested
+        // tuple components over and over
+        let (i, ty) = parse_simple_type(components.clone(), max_depth)(input)?;
+
+        parse_array(ty, max_depth, i)
     }
 }
 
 fn parse_simple_type(
     components: Rc<Option<Vec<ParamEntry>>>,
+    max_depth: usize,
 ) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
     move |input: &str| {
         alt((
-            parse_tuple(components.clone()),
+            parse_tuple(components.clone(), max_depth),
             parse_uint,
             parse_int,
             parse_address,
@@ -238,13 +379,14 @@ fn parse_simple_type(
 
 fn parse_uint(input: &str) -> TypeParseResult<&str, Type> {
     map_error(
-        verify(parse_sized("uint"), check_int_size)(input).map(|(i, size)| (i, Type::Uint(size))),
+        verify(parse_int_sized("uint"), check_int_size)(input)
+            .map(|(i, size)| (i, Type::Uint(size))),
     )
 }
 
 fn parse_int(input: &str) -> TypeParseResult<&str, Type> {
     map_error(
-        verify(parse_sized("int"), check_int_size)(input).map(|(i, size)| (i, Type::Int(size))),
+        verify(parse_int_sized("int"), check_int_size)(input).map(|(i, size)| (i, Type::Int(size))),
     )
 }
 
@@ -269,30 +411,27 @@ fn parse_bytes(input: &str) -> TypeParseResult<&str, Type> {
     Ok((i, ty))
 }
 
-fn parse_array(
-    components: Rc<Option<Vec<ParamEntry>>>,
-) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
-    move |input: &str| {
-        let (i, ty) = parse_simple_type(components.clone())(input)?;
-
-        let (i, sizes) = map_error(many1(delimited(char('['), opt(parse_integer), char(']')))(
-            i,
-        ))?;
+// Parses the array suffixes (e.g. `[2][]`) following an element type.
+fn parse_array(ty: Type, max_depth: usize, input: &str) -> TypeParseResult<&str, Type> {
+    let (i, sizes) = map_error(many0(delimited(char('['), opt(parse_integer), char(']')))(
+        input,
+    ))?;
 
-        let array_from_size = |ty: Type, size: Option<usize>| match size {
-            None => Type::Array(Box::new(ty)),
-            Some(size) => Type::FixedArray(Box::new(ty), size),
-        };
+    if ty.depth() + sizes.len() > max_depth {
+        return Err(nom::Err::Failure(TypeParseError::MaxDepthExceeded));
+    }
 
-        let init_arr_ty = array_from_size(ty, sizes[0]);
-        let arr_ty = sizes.into_iter().skip(1).fold(init_arr_ty, array_from_size);
+    let arr_ty = sizes.into_iter().fold(ty, |ty, size| match size {
+        None => Type::Array(Box::new(ty)),
+        Some(size) => Type::FixedArray(Box::new(ty), size),
+    });
 
-        Ok((i, arr_ty))
-    }
+    Ok((i, arr_ty))
 }
 
 fn parse_tuple(
     components: Rc<Option<Vec<ParamEntry>>>,
+    max_depth: usize,
 ) -> impl Fn(&str) -> TypeParseResult<&str, Type> {
     move |input: &str| {
         let (i, _) = map_error(tag("tuple")(input))?;
@@ -304,10 +443,16 @@ fn parse_tuple(
                 .try_fold(vec![], |mut param_tys, param| {
                     let comps = param.components.as_ref().cloned();
 
-                    let ty = match parse_exact_type(Rc::new(comps), &param.type_) {
-                        Ok((_, ty)) => ty,
-                        Err(_) => return Err(nom::Err::Failure(TypeParseError::Error)),
-                    };
+                    let component_max_depth = max_depth.saturating_sub(1);
+
+                    let ty =
+                        match parse_exact_type(Rc::new(comps), &param.type_, component_max_depth) {
+                            Ok((_, ty)) => ty,
+                            Err(nom::Err::Failure(TypeParseError::MaxDepthExceeded)) => {
+                                return Err(nom::Err::Failure(TypeParseError::MaxDepthExceeded))
+                            }
+                            Err(_) => return Err(nom::Err::Failure(TypeParseError::Error)),
+                        };
 
                     param_tys.push((param.name, ty));
 
@@ -321,11 +466,13 @@ fn parse_tuple(
     }
 }
 
-fn parse_sized(t: &str) -> impl Fn(&str) -> IResult<&str, usize> + '_ {
+// Parses int<M>/uint<M> sizes. The bare `int`/`uint` forms are aliases for size 256.
+fn parse_int_sized(t: &str) -> impl Fn(&str) -> IResult<&str, usize> + '_ {
     move |input: &str| {
         let (i, _) = tag(t)(input)?;
+        let (i, size) = opt(parse_integer)(i)?;
 
-        parse_integer(i)
+        Ok((i, size.unwrap_or(256)))
     }
 }
 
@@ -333,13 +480,13 @@ fn parse_integer(input: &str) -> IResult<&str, usize> {
     map_res(recognize(many1(digit1)), str::parse)(input)
 }
 
-fn check_int_size(i: &usize) -> bool {
+pub(crate) fn check_int_size(i: &usize) -> bool {
     let i = *i;
 
     i > 0 && i <= 256 && i % 8 == 0
 }
 
-fn check_fixed_bytes_size(i: &usize) -> bool {
+pub(crate) fn check_fixed_bytes_size(i: &usize) -> bool {
     let i = *i;
 
     i > 0 && i <= 32
@@ -352,6 +499,84 @@ mod test {
     use pretty_assertions::assert_eq;
     use serde_json::json;
 
+    #[test]
+    fn decoded_params_decode() {
+        let params = vec![
+            Param {
+                name: "a".to_string(),
+                type_: Type::Uint(256),
+                indexed: None,
+            },
+            Param {
+                name: "b".to_string(),
+                type_: Type::String,
+                indexed: Some(false),
+            },
+        ];
+
+        let values = vec![
+            Value::Uint(ethereum_types::U256::from(7), 256),
+            Value::String("abc".to_string()),
+        ];
+
+        let decoded =
+            DecodedParams::decode(&params, &Value::encode(&values)).expect("decode failed");
+
+        assert_eq!(
+            decoded,
+            DecodedParams::from(params.iter().cloned().zip(values).collect::<Vec<_>>())
+        );
+
+        assert!(DecodedParams::decode(&params[..1], &[0u8; 16]).is_err());
+    }
+
+    #[test]
+    fn decoded_params_accessors() {
+        let a = Value::Uint(ethereum_types::U256::from(7), 256);
+        let b = Value::String("abc".to_string());
+
+        let decoded = DecodedParams::from(vec![
+            (
+                Param {
+                    name: "a".to_string(),
+                    type_: Type::Uint(256),
+                    indexed: None,
+                },
+                a.clone(),
+            ),
+            (
+                Param {
+                    name: "b".to_string(),
+                    type_: Type::String,
+                    indexed: None,
+                },
+                b.clone(),
+            ),
+        ]);
+
+        assert_eq!(decoded.get("a"), Some(&a));
+        assert_eq!(decoded.get("b"), Some(&b));
+        assert_eq!(decoded.get("c"), None);
+
+        assert_eq!(decoded[0].value, a);
+
+        assert_eq!(decoded.values().collect::<Vec<_>>(), vec![&a, &b]);
+    }
+
+    #[test]
+    fn param_is_dynamic() {
+        let param = |type_| Param {
+            name: String::new(),
+            type_,
+            indexed: None,
+        };
+
+        assert!(!param(Type::Uint(256)).is_dynamic());
+        assert!(!param(Type::FixedArray(Box::new(Type::Address), 2)).is_dynamic());
+        assert!(param(Type::String).is_dynamic());
+        assert!(param(Type::Tuple(vec![("a".into(), Type::Bytes)])).is_dynamic());
+    }
+
     #[test]
     fn serde_uint() {
         for i in (8..=256).step_by(8) {
@@ -402,6 +627,131 @@ mod test {
         }
     }
 
+    #[test]
+    fn serde_int_aliases() {
+        for (alias, ty) in [("uint", Type::Uint(256)), ("int", Type::Int(256))] {
+            let v = json!({
+                "name": "a",
+                "type": alias,
+            });
+
+            let param: Param = serde_json::from_value(v).expect("param deserialized");
+
+            assert_eq!(
+                param,
+                Param {
+                    name: "a".to_string(),
+                    type_: ty,
+                    indexed: None
+                }
+            );
+        }
+
+        let param: Param = serde_json::from_value(json!({
+            "name": "a",
+            "type": "uint[]",
+        }))
+        .expect("param deserialized");
+
+        assert_eq!(param.type_, Type::Array(Box::new(Type::Uint(256))));
+    }
+
+    #[test]
+    fn type_from_str() {
+        assert_eq!("uint".parse::<Type>().unwrap(), Type::Uint(256));
+        assert_eq!("int".parse::<Type>().unwrap(), Type::Int(256));
+        assert_eq!("int8".parse::<Type>().unwrap(), Type::Int(8));
+        assert_eq!(
+            "bytes32[2][]".parse::<Type>().unwrap(),
+            Type::Array(Box::new(Type::FixedArray(
+                Box::new(Type::FixedBytes(32)),
+                2
+            )))
+        );
+
+        assert!("uint7".parse::<Type>().is_err());
+        assert!("uint256x".parse::<Type>().is_err());
+        assert!("tuple".parse::<Type>().is_err());
+    }
+
+    #[test]
+    fn round_int_sizes() {
+        let mut entry: ParamEntry = serde_json::from_value(json!({
+            "name": "s",
+            "type": "tuple[]",
+            "components": [
+                {"name": "a", "type": "uint7[2]"},
+                {"name": "b", "type": "int255"},
+                {"name": "c", "type": "uint8"},
+                {"name": "d", "type": "uint257"},
+            ],
+        }))
+        .unwrap();
+
+        let mut fixups = vec![];
+        entry.round_int_sizes(&mut fixups);
+
+        let types: Vec<_> = entry
+            .components
+            .unwrap()
+            .into_iter()
+            .map(|component| component.type_)
+            .collect();
+        assert_eq!(types, vec!["uint8[2]", "int256", "uint8", "uint257"]);
+        assert_eq!(
+            fixups,
+            vec![
+                ("uint7[2]".to_string(), "uint8[2]".to_string()),
+                ("int255".to_string(), "int256".to_string())
+            ]
+        );
+
+        let err = serde_json::from_value::<Param>(json!({"name": "x", "type": "uint7"}))
+            .expect_err("param deserialized");
+        assert!(err
+            .to_string()
+            .contains("invalid type uint7: int sizes must be multiples of 8 between 8 and 256"));
+    }
+
+    #[test]
+    fn type_max_depth() {
+        let ty = format!("uint{}", "[]".repeat(DEFAULT_MAX_DEPTH - 1));
+        assert_eq!(ty.parse::<Type>().unwrap().depth(), DEFAULT_MAX_DEPTH);
+
+        let ty = format!("uint{}", "[]".repeat(100_000));
+        let err = ty.parse::<Type>().expect_err("type parsed");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::MaxDepthExceeded {
+                max_depth: DEFAULT_MAX_DEPTH
+            })
+        );
+
+        let v = json!({
+            "name": "a",
+            "type": format!("uint{}", "[]".repeat(DEFAULT_MAX_DEPTH)),
+        });
+        let err = serde_json::from_value::<Param>(v).expect_err("param deserialized");
+        assert!(err.to_string().contains("maximum depth"));
+
+        // nested tuple components count towards the depth as well
+        let nested = (0..DEFAULT_MAX_DEPTH).fold(
+            json!({"name": "x", "type": "uint256"}),
+            |c, _| json!({"name": "t", "type": "tuple", "components": [c]}),
+        );
+        let err = serde_json::from_value::<Param>(nested).expect_err("param deserialized");
+        assert!(err.to_string().contains("maximum depth"));
+
+        let entry = ParamEntry {
+            name: "a".to_string(),
+            type_: "uint256[][]".to_string(),
+            indexed: None,
+            components: None,
+        };
+        assert!(Param::from_entry(entry.clone(), 3).is_ok());
+        assert!(Param::from_entry(entry, 2).is_err());
+    }
+
     #[test]
     fn serde_address() {
         let v = json!({
@@ -0,0 +1,144 @@
+use alloc::string::String;
+use ethereum_types::U256;
+
+use crate::{Type, Value};
+
+/// `Error(string)` selector.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// `Panic(uint256)` selector.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Reason of a reverted call, as encoded by Solidity's built-in errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RevertReason {
+    /// `Error(string)`, raised by `revert("...")` and `require(..., "...")`.
+    Error(String),
+    /// `Panic(uint256)`, raised on failed assertions, arithmetic overflows, etc.
+    Panic(u64),
+}
+
+impl RevertReason {
+    /// Decodes the reason from the return data of a reverted call.
+    ///
+    /// Returns `None` if the data is not a well formed `Error(string)` or
+    /// `Panic(uint256)`, e.g. for custom errors.
+    pub fn decode(data: &[u8]) -> Option<RevertReason> {
+        if data.len() < 4 {
+            return None;
+        }
+
+        let (selector, args) = data.split_at(4);
+
+        if selector == ERROR_SELECTOR {
+            match Value::decode_from_slice(args, &[Type::String])
+                .ok()?
+                .pop()?
+            {
+                Value::String(reason) => Some(RevertReason::Error(reason)),
+                _ => None,
+            }
+        } else if selector == PANIC_SELECTOR {
+            match Value::decode_from_slice(args, &[Type::Uint(256)])
+                .ok()?
+                .pop()?
+            {
+                Value::Uint(code, _) if code <= U256::from(u64::MAX) => {
+                    Some(RevertReason::Panic(code.as_u64()))
+                }
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a description of the panic code, if this is a panic with a code known
+    /// to the Solidity compiler.
+    pub fn panic_description(&self) -> Option<&'static str> {
+        let description = match self {
+            RevertReason::Error(_) => return None,
+            RevertReason::Panic(0x00) => "generic compiler inserted panic",
+            RevertReason::Panic(0x01) => "assertion failed",
+            RevertReason::Panic(0x11) => "arithmetic overflow or underflow",
+            RevertReason::Panic(0x12) => "division or modulo by zero",
+            RevertReason::Panic(0x21) => "invalid enum value",
+            RevertReason::Panic(0x22) => "incorrectly encoded storage byte array",
+            RevertReason::Panic(0x31) => "pop on empty array",
+            RevertReason::Panic(0x32) => "array index out of bounds",
+            RevertReason::Panic(0x41) => "too much memory allocated",
+            RevertReason::Panic(0x51) => "call to zero initialized internal function",
+            RevertReason::Panic(_) => return None,
+        };
+
+        Some(description)
+    }
+}
+
+impl core::fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RevertReason::Error(reason) => write!(f, "{}", reason),
+            RevertReason::Panic(code) => match self.panic_description() {
+                Some(description) => write!(f, "panic: {} (0x{:02x})", description, code),
+                None => write!(f, "panic: 0x{:02x}", code),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn decode_error() {
+        // revert("Ownable: caller is not the owner")
+        let data = hex::decode("08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000204f776e61626c653a2063616c6c6572206973206e6f7420746865206f776e6572").unwrap();
+
+        let reason = RevertReason::decode(&data).expect("revert reason not decoded");
+
+        assert_eq!(
+            reason,
+            RevertReason::Error("Ownable: caller is not the owner".to_string())
+        );
+        assert_eq!(reason.panic_description(), None);
+        assert_eq!(reason.to_string(), "Ownable: caller is not the owner");
+    }
+
+    #[test]
+    fn decode_panic() {
+        let data =
+            hex::decode("4e487b710000000000000000000000000000000000000000000000000000000000000011")
+                .unwrap();
+
+        let reason = RevertReason::decode(&data).expect("revert reason not decoded");
+
+        assert_eq!(reason, RevertReason::Panic(0x11));
+        assert_eq!(
+            reason.panic_description(),
+            Some("arithmetic overflow or underflow")
+        );
+        assert_eq!(
+            reason.to_string(),
+            "panic: arithmetic overflow or underflow (0x11)"
+        );
+        assert_eq!(RevertReason::Panic(0x99).to_string(), "panic: 0x99");
+    }
+
+    #[test]
+    fn decode_other() {
+        assert_eq!(RevertReason::decode(&[]), None);
+        assert_eq!(RevertReason::decode(&ERROR_SELECTOR), None);
+        assert_eq!(RevertReason::decode(&PANIC_SELECTOR[..3]), None);
+
+        // custom error
+        assert_eq!(RevertReason::decode(&[0x12, 0x34, 0x56, 0x78]), None);
+
+        // panic code doesn't fit in 64 bits
+        let mut data = PANIC_SELECTOR.to_vec();
+        data.extend([0xff; 32]);
+        assert_eq!(RevertReason::decode(&data), None);
+    }
+}
@@ -0,0 +1,390 @@
+//! EIP-712 typed structured data hashing.
+//!
+//! Structs are described by `Type::Tuple` types whose components are the struct's
+//! named members. Only the subset of EIP-712 whose members are atomic types
+//! (`uint<M>`, `int<M>`, `address`, `bool` and `bytes<M>`), `string`, `bytes` and
+//! arrays of those is supported: tuple types carry no struct name, so structs
+//! nested in other structs can't be referenced by their type and are rejected.
+//!
+//! The final signing payload is built from the hash of the signed struct and the
+//! separator of an [`Eip712Domain`] with [`eip712_digest`].
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use anyhow::{anyhow, Result};
+use ethereum_types::{H160, H256, U256};
+
+use crate::{AbiError, Keccak256, TinyKeccak, Type, Value};
+
+/// EIP-712 domain, i.e. the `EIP712Domain` struct.
+///
+/// Only the fields that are set are part of the domain's type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Eip712Domain {
+    /// Name of the signing domain, e.g. the DApp or protocol name.
+    pub name: Option<String>,
+    /// Current major version of the signing domain.
+    pub version: Option<String>,
+    /// EIP-155 chain id.
+    pub chain_id: Option<U256>,
+    /// Address of the contract that verifies the signature.
+    pub verifying_contract: Option<H160>,
+    /// Disambiguating salt for the protocol.
+    pub salt: Option<H256>,
+}
+
+impl Eip712Domain {
+    /// Creates a domain without any fields set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the domain name.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the domain version.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Sets the domain chain id.
+    pub fn chain_id(mut self, chain_id: impl Into<U256>) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
+    /// Sets the domain verifying contract.
+    pub fn verifying_contract(mut self, verifying_contract: H160) -> Self {
+        self.verifying_contract = Some(verifying_contract);
+        self
+    }
+
+    /// Sets the domain salt.
+    pub fn salt(mut self, salt: H256) -> Self {
+        self.salt = Some(salt);
+        self
+    }
+
+    /// Computes the domain separator, i.e. the `hashStruct` of the domain.
+    pub fn separator(&self) -> H256 {
+        let fields = vec![
+            self.name
+                .as_ref()
+                .map(|name| ("name", Value::String(name.clone()))),
+            self.version
+                .as_ref()
+                .map(|version| ("version", Value::String(version.clone()))),
+            self.chain_id
+                .map(|chain_id| ("chainId", Value::Uint(chain_id, 256))),
+            self.verifying_contract
+                .map(|addr| ("verifyingContract", Value::Address(addr))),
+            self.salt
+                .map(|salt| ("salt", Value::FixedBytes(salt.as_bytes().to_vec()))),
+        ];
+
+        let value = Value::Tuple(
+            fields
+                .into_iter()
+                .flatten()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        );
+
+        hash_struct("EIP712Domain", &value.type_of(), &value)
+            .expect("domain fields are supported struct members")
+    }
+}
+
+/// Computes the EIP-712 digest to be signed, i.e.
+/// `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+pub fn eip712_digest(domain_separator: H256, struct_hash: H256) -> H256 {
+    let mut buf = vec![0x19, 0x01];
+    buf.extend_from_slice(domain_separator.as_bytes());
+    buf.extend_from_slice(struct_hash.as_bytes());
+
+    H256::from(TinyKeccak::keccak256(&buf))
+}
+
+/// Returns the EIP-712 encoded type of the struct with the given name and tuple
+/// type, e.g. `Mail(address from,address to,string contents)`.
+pub fn encode_type(name: &str, ty: &Type) -> Result<String> {
+    let members = struct_members(ty)?;
+
+    Ok(format!(
+        "{}({})",
+        name,
+        members
+            .iter()
+            .map(|(name, ty)| format!("{} {}", ty, name))
+            .collect::<Vec<_>>()
+            .join(",")
+    ))
+}
+
+/// Returns the EIP-712 type hash of the struct with the given name and tuple type,
+/// i.e. the Keccak-256 hash of its encoded type.
+pub fn type_hash(name: &str, ty: &Type) -> Result<H256> {
+    let encoded_type = encode_type(name, ty)?;
+
+    Ok(H256::from(TinyKeccak::keccak256(encoded_type.as_bytes())))
+}
+
+/// Returns the EIP-712 `hashStruct` of a struct value with the given name and
+/// tuple type.
+///
+/// The value must be a tuple of the given type, otherwise
+/// [`AbiError::UnexpectedValue`] is returned.
+pub fn hash_struct(name: &str, ty: &Type, value: &Value) -> Result<H256> {
+    let type_hash = type_hash(name, ty)?;
+
+    let got = value.type_of();
+    if got != *ty {
+        return Err(AbiError::UnexpectedValue {
+            expected: ty.clone(),
+            got,
+        }
+        .into());
+    }
+
+    let mut buf = type_hash.as_bytes().to_vec();
+    if let Value::Tuple(values) = value {
+        for (_, value) in values {
+            encode_data(value, &mut buf);
+        }
+    }
+
+    Ok(H256::from(TinyKeccak::keccak256(&buf)))
+}
+
+// Returns the members of a struct type, checking they are all supported.
+fn struct_members(ty: &Type) -> Result<&[(String, Type)]> {
+    let members = match ty {
+        Type::Tuple(members) => members,
+        _ => return Err(anyhow!("expected a tuple type for struct, got {}", ty)),
+    };
+
+    for (name, ty) in members {
+        if name.is_empty() {
+            return Err(anyhow!("struct member of type {} has no name", ty));
+        }
+
+        check_member_type(ty)?;
+    }
+
+    Ok(members)
+}
+
+// Checks that a struct member type is in the supported EIP-712 subset.
+fn check_member_type(ty: &Type) -> Result<()> {
+    match ty {
+        Type::FixedArray(ty, _) | Type::Array(ty) => check_member_type(ty),
+        Type::Tuple(_) => Err(anyhow!("nested struct members are not supported")),
+        _ => Ok(()),
+    }
+}
+
+// Appends the 32 bytes EIP-712 encoding of a struct member value.
+fn encode_data(value: &Value, buf: &mut Vec<u8>) {
+    match value {
+        Value::String(s) => buf.extend(TinyKeccak::keccak256(s.as_bytes())),
+
+        Value::Bytes(bytes) => buf.extend(TinyKeccak::keccak256(bytes)),
+
+        Value::FixedArray(values, _) | Value::Array(values, _) => {
+            let mut elements = vec![];
+            for value in values {
+                encode_data(value, &mut elements);
+            }
+
+            buf.extend(TinyKeccak::keccak256(&elements));
+        }
+
+        // atomic values are encoded as their ABI encoding
+        _ => Value::encode_into(core::slice::from_ref(value), buf),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+    use core::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn person_type() -> Type {
+        Type::Tuple(vec![
+            ("name".to_string(), Type::String),
+            ("wallet".to_string(), Type::Address),
+        ])
+    }
+
+    fn person(name: &str, wallet: &str) -> Value {
+        Value::Tuple(vec![
+            ("name".to_string(), Value::String(name.to_string())),
+            (
+                "wallet".to_string(),
+                Value::Address(H160::from_str(wallet).unwrap()),
+            ),
+        ])
+    }
+
+    #[test]
+    fn eip712_person() {
+        // from the EIP-712 example
+        assert_eq!(
+            encode_type("Person", &person_type()).unwrap(),
+            "Person(string name,address wallet)"
+        );
+
+        let cow = person("Cow", "CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826");
+        assert_eq!(
+            hash_struct("Person", &person_type(), &cow).unwrap(),
+            H256::from_str("fc71e5fa27ff56c350aa531bc129ebdf613b772b6604664f5d8dbe21b85eb0c8")
+                .unwrap()
+        );
+
+        let bob = person("Bob", "bBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB");
+        assert_eq!(
+            hash_struct("Person", &person_type(), &bob).unwrap(),
+            H256::from_str("cd54f074a4af31b4411ff6a60c9719dbd559c221c8ac3492d9d872b041d703d1")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn eip712_mail_digest() {
+        // from the EIP-712 example
+        let domain = Eip712Domain::new()
+            .name("Ether Mail")
+            .version("1")
+            .chain_id(1)
+            .verifying_contract(
+                H160::from_str("CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC").unwrap(),
+            );
+
+        assert_eq!(
+            domain.separator(),
+            H256::from_str("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+                .unwrap()
+        );
+
+        // hashStruct of the example mail
+        let mail_hash =
+            H256::from_str("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+                .unwrap();
+
+        assert_eq!(
+            eip712_digest(domain.separator(), mail_hash),
+            H256::from_str("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn domain_fields() {
+        let salt = H256::repeat_byte(0x11);
+        let domain = Eip712Domain::new().chain_id(5).salt(salt);
+
+        let ty = Type::Tuple(vec![
+            ("chainId".to_string(), Type::Uint(256)),
+            ("salt".to_string(), Type::FixedBytes(32)),
+        ]);
+        assert_eq!(
+            encode_type("EIP712Domain", &ty).unwrap(),
+            "EIP712Domain(uint256 chainId,bytes32 salt)"
+        );
+
+        let value = Value::Tuple(vec![
+            ("chainId".to_string(), Value::Uint(U256::from(5), 256)),
+            (
+                "salt".to_string(),
+                Value::FixedBytes(salt.as_bytes().to_vec()),
+            ),
+        ]);
+        assert_eq!(
+            domain.separator(),
+            hash_struct("EIP712Domain", &ty, &value).unwrap()
+        );
+    }
+
+    #[test]
+    fn arrays() {
+        let ty = Type::Tuple(vec![
+            ("ids".to_string(), Type::Array(Box::new(Type::Uint(256)))),
+            (
+                "tags".to_string(),
+                Type::FixedArray(Box::new(Type::String), 2),
+            ),
+        ]);
+        let value = Value::Tuple(vec![
+            (
+                "ids".to_string(),
+                Value::Array(
+                    vec![
+                        Value::Uint(U256::from(1), 256),
+                        Value::Uint(U256::from(2), 256),
+                    ],
+                    Type::Uint(256),
+                ),
+            ),
+            (
+                "tags".to_string(),
+                Value::FixedArray(
+                    vec![
+                        Value::String("a".to_string()),
+                        Value::String("b".to_string()),
+                    ],
+                    Type::String,
+                ),
+            ),
+        ]);
+
+        assert_eq!(
+            encode_type("Batch", &ty).unwrap(),
+            "Batch(uint256[] ids,string[2] tags)"
+        );
+
+        let mut expected = type_hash("Batch", &ty).unwrap().as_bytes().to_vec();
+        expected.extend(TinyKeccak::keccak256(&Value::encode(&[
+            Value::Uint(U256::from(1), 256),
+            Value::Uint(U256::from(2), 256),
+        ])));
+        let mut tags = TinyKeccak::keccak256(b"a").to_vec();
+        tags.extend(TinyKeccak::keccak256(b"b"));
+        expected.extend(TinyKeccak::keccak256(&tags));
+
+        assert_eq!(
+            hash_struct("Batch", &ty, &value).unwrap(),
+            H256::from(TinyKeccak::keccak256(&expected))
+        );
+    }
+
+    #[test]
+    fn unsupported_types_and_values() {
+        assert!(encode_type("S", &Type::Uint(256)).is_err());
+        assert!(encode_type("S", &Type::Tuple(vec![("".to_string(), Type::Bool)])).is_err());
+
+        let nested = Type::Tuple(vec![("from".to_string(), person_type())]);
+        assert!(type_hash("Mail", &nested).is_err());
+
+        let err = hash_struct("Person", &person_type(), &Value::Bool(true))
+            .expect_err("hash_struct succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::UnexpectedValue {
+                expected: person_type(),
+                got: Type::Bool,
+            })
+        );
+    }
+}
@@ -1,5 +1,20 @@
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Default maximum type nesting depth accepted when parsing ABIs and decoding values.
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
 /// Available ABI types.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Types are ordered by variant first (in declaration order) and then by their
+/// sizes and element types, which is enough to sort them or use them as
+/// `BTreeMap` keys. Tuples compare their fields in order, each by name and then
+/// by type.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Type {
     /// Unsigned int type (uint<M>).
     Uint(usize),
@@ -39,10 +54,59 @@ impl Type {
             Type::Tuple(tys) => tys.iter().any(|(_, ty)| ty.is_dynamic()),
         }
     }
+
+    /// Returns the nesting depth of the type.
+    ///
+    /// Elementary types have depth 1, and every enclosing array or tuple adds one,
+    /// e.g. `uint256[][]` has depth 3.
+    pub fn depth(&self) -> usize {
+        match self {
+            Type::FixedArray(ty, _) | Type::Array(ty) => ty.depth() + 1,
+            Type::Tuple(tys) => tys.iter().map(|(_, ty)| ty.depth()).max().unwrap_or(0) + 1,
+            _ => 1,
+        }
+    }
+
+    /// Returns the element type of an array or fixed array type, or `None` for any
+    /// other type.
+    pub fn element_type(&self) -> Option<&Type> {
+        match self {
+            Type::FixedArray(ty, _) | Type::Array(ty) => Some(ty),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of bytes a value of this type takes in the head region of
+    /// an encoding.
+    ///
+    /// Dynamic types take 32 bytes since only their offset pointer is stored in the
+    /// head, while static types are stored inline. The size saturates at
+    /// `usize::MAX` for fixed arrays too large to address.
+    pub fn head_size(&self) -> usize {
+        if self.is_dynamic() {
+            return 32;
+        }
+
+        match self {
+            Type::FixedArray(ty, size) => ty.head_size().saturating_mul(*size),
+            Type::Tuple(tys) => tys
+                .iter()
+                .fold(0, |acc: usize, (_, ty)| acc.saturating_add(ty.head_size())),
+            _ => 32,
+        }
+    }
+
+    /// Returns the canonical type string used by solc when computing function
+    /// selectors and event topics, e.g. `(uint256,(address,bool)[])`.
+    ///
+    /// Tuple component names are omitted. This is the same as the `Display` output.
+    pub fn canonical_string(&self) -> String {
+        self.to_string()
+    }
 }
 
-impl std::fmt::Display for Type {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Type {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Type::Uint(size) => write!(f, "uint{}", size),
             Type::Int(size) => write!(f, "int{}", size),
@@ -64,3 +128,171 @@ impl std::fmt::Display for Type {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn hash_map_key() {
+        let mut map = std::collections::HashMap::new();
+
+        map.insert(Type::Uint(256), "uint256");
+        map.insert(Type::Array(Box::new(Type::Uint(256))), "uint256[]");
+
+        assert_eq!(map.get(&Type::Uint(256)), Some(&"uint256"));
+        assert_eq!(
+            map.get(&Type::Array(Box::new(Type::Uint(256)))),
+            Some(&"uint256[]")
+        );
+        assert_eq!(map.get(&Type::Uint(128)), None);
+    }
+
+    #[test]
+    fn ordering() {
+        let mut tys = vec![
+            Type::Array(Box::new(Type::Uint(8))),
+            Type::Bool,
+            Type::Uint(256),
+            Type::Uint(8),
+            Type::Int(8),
+            Type::Array(Box::new(Type::Address)),
+        ];
+        tys.sort();
+
+        assert_eq!(
+            tys,
+            vec![
+                Type::Uint(8),
+                Type::Uint(256),
+                Type::Int(8),
+                Type::Bool,
+                Type::Array(Box::new(Type::Uint(8))),
+                Type::Array(Box::new(Type::Address)),
+            ]
+        );
+
+        let tuple = |fields: &[(&str, Type)]| {
+            Type::Tuple(
+                fields
+                    .iter()
+                    .map(|(name, ty)| (name.to_string(), ty.clone()))
+                    .collect(),
+            )
+        };
+        let mut tys = vec![
+            tuple(&[("b", Type::Bool)]),
+            tuple(&[("a", Type::Bool), ("c", Type::Bool)]),
+            tuple(&[("a", Type::Bool)]),
+            tuple(&[("a", Type::Address)]),
+        ];
+        tys.sort();
+
+        assert_eq!(
+            tys,
+            vec![
+                tuple(&[("a", Type::Address)]),
+                tuple(&[("a", Type::Bool)]),
+                tuple(&[("a", Type::Bool), ("c", Type::Bool)]),
+                tuple(&[("b", Type::Bool)]),
+            ]
+        );
+
+        let set: std::collections::BTreeSet<_> = vec![Type::String, Type::Bytes, Type::String]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn canonical_string() {
+        assert_eq!(Type::Uint(256).canonical_string(), "uint256");
+        assert_eq!(
+            Type::FixedArray(Box::new(Type::FixedBytes(32)), 2).canonical_string(),
+            "bytes32[2]"
+        );
+
+        let ty = Type::Tuple(vec![
+            ("a".to_string(), Type::Uint(256)),
+            (
+                "b".to_string(),
+                Type::Array(Box::new(Type::Tuple(vec![
+                    ("x".to_string(), Type::Address),
+                    ("y".to_string(), Type::Bool),
+                ]))),
+            ),
+        ]);
+        assert_eq!(ty.canonical_string(), "(uint256,(address,bool)[])");
+        assert_eq!(ty.canonical_string(), ty.to_string());
+    }
+
+    #[test]
+    fn depth() {
+        assert_eq!(Type::Address.depth(), 1);
+        assert_eq!(Type::Tuple(vec![]).depth(), 1);
+
+        let ty = Type::Array(Box::new(Type::FixedArray(Box::new(Type::Uint(256)), 2)));
+        assert_eq!(ty.depth(), 3);
+
+        let ty = Type::Tuple(vec![("a".to_string(), Type::Bool), ("b".to_string(), ty)]);
+        assert_eq!(ty.depth(), 4);
+    }
+
+    #[test]
+    fn element_type() {
+        let ty = Type::Array(Box::new(Type::FixedArray(Box::new(Type::Address), 2)));
+        assert_eq!(
+            ty.element_type(),
+            Some(&Type::FixedArray(Box::new(Type::Address), 2))
+        );
+        assert_eq!(
+            ty.element_type().and_then(Type::element_type),
+            Some(&Type::Address)
+        );
+        assert_eq!(Type::Address.element_type(), None);
+        assert_eq!(Type::Bytes.element_type(), None);
+    }
+
+    #[test]
+    fn head_size() {
+        assert_eq!(Type::Uint(8).head_size(), 32);
+        assert_eq!(Type::FixedBytes(4).head_size(), 32);
+        assert_eq!(Type::String.head_size(), 32);
+        assert_eq!(Type::Array(Box::new(Type::Uint(256))).head_size(), 32);
+
+        let fixed_arr = Type::FixedArray(Box::new(Type::Uint(256)), 3);
+        assert_eq!(fixed_arr.head_size(), 96);
+
+        let nested_fixed_arr = Type::FixedArray(Box::new(fixed_arr.clone()), 2);
+        assert_eq!(nested_fixed_arr.head_size(), 192);
+
+        let tuple = Type::Tuple(vec![
+            ("a".to_string(), Type::Address),
+            (
+                "b".to_string(),
+                Type::Tuple(vec![
+                    ("x".to_string(), Type::Bool),
+                    ("y".to_string(), fixed_arr),
+                ]),
+            ),
+        ]);
+        assert_eq!(tuple.head_size(), 160);
+
+        let dynamic_tuple = Type::Tuple(vec![
+            ("a".to_string(), Type::Address),
+            ("b".to_string(), Type::Bytes),
+        ]);
+        assert_eq!(dynamic_tuple.head_size(), 32);
+        assert_eq!(Type::FixedArray(Box::new(dynamic_tuple), 2).head_size(), 32);
+
+        let huge_arr = Type::FixedArray(Box::new(Type::Uint(256)), usize::MAX / 16);
+        assert_eq!(huge_arr.head_size(), usize::MAX);
+        let huge_tuple = Type::Tuple(vec![
+            ("a".to_string(), huge_arr.clone()),
+            ("b".to_string(), huge_arr),
+        ]);
+        assert_eq!(huge_tuple.head_size(), usize::MAX);
+    }
+}
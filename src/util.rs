@@ -0,0 +1,59 @@
+use alloc::vec::Vec;
+use anyhow::{anyhow, Result};
+
+/// Decodes a hex string, with or without a `0x`/`0X` prefix. Surrounding
+/// whitespace is ignored.
+///
+/// ```
+/// assert_eq!(ethereum_abi::hex_to_bytes("0xa1b2").unwrap(), vec![0xa1, 0xb2]);
+/// ```
+pub fn hex_to_bytes(input: &str) -> Result<Vec<u8>> {
+    let input = input.trim();
+    let digits = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+        .unwrap_or(input);
+
+    if digits.len() % 2 != 0 {
+        return Err(anyhow!(
+            "hex input has an odd number of digits ({})",
+            digits.len()
+        ));
+    }
+
+    hex::decode(digits).map_err(|err| anyhow!("invalid hex input: {}", err))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn decode_hex_prefixes() {
+        assert_eq!(hex_to_bytes("a1b2").unwrap(), vec![0xa1, 0xb2]);
+        assert_eq!(hex_to_bytes("0xa1b2").unwrap(), vec![0xa1, 0xb2]);
+        assert_eq!(hex_to_bytes("0XA1B2").unwrap(), vec![0xa1, 0xb2]);
+        assert_eq!(hex_to_bytes("0x").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn decode_hex_whitespace() {
+        assert_eq!(hex_to_bytes(" 0xa1b2\n").unwrap(), vec![0xa1, 0xb2]);
+        assert_eq!(hex_to_bytes("\ta1b2 ").unwrap(), vec![0xa1, 0xb2]);
+        assert!(hex_to_bytes("0x a1b2").is_err());
+    }
+
+    #[test]
+    fn decode_hex_odd_length() {
+        let err = hex_to_bytes("0xa1b").expect_err("hex_to_bytes succeeded");
+
+        assert_eq!(err.to_string(), "hex input has an odd number of digits (3)");
+    }
+
+    #[test]
+    fn decode_hex_invalid_digits() {
+        assert!(hex_to_bytes("0xzz").is_err());
+    }
+}
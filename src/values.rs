@@ -1,14 +1,271 @@
+use alloc::{
+    boxed::Box,
+    collections::VecDeque,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use anyhow::{anyhow, Result};
+use core::ops::Range;
 use ethereum_types::{H160, U256};
 
-use crate::types::Type;
+use crate::{
+    params::{check_fixed_bytes_size, check_int_size},
+    types::{Type, DEFAULT_MAX_DEPTH},
+    AbiError,
+};
+
+/// Options controlling how values are decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Whether to reject input that is not canonically encoded, e.g. nonzero
+    /// padding bytes, bool words other than 0 and 1, strings that are not
+    /// valid UTF-8 or offsets pointing back into the head region.
+    ///
+    /// When disabled (the default), padding bytes are ignored and invalid UTF-8
+    /// sequences in strings are replaced with `U+FFFD`.
+    pub strict: bool,
+    /// Maximum nesting depth of the types to decode (see [`Type::depth`]).
+    ///
+    /// Defaults to [`DEFAULT_MAX_DEPTH`].
+    pub max_depth: usize,
+    /// Maximum number of values to decode, counting every array element and tuple
+    /// field along with the top level values.
+    ///
+    /// Array lengths are checked against it before decoding their elements, so
+    /// untrusted input declaring huge arrays is rejected early. Defaults to `None`,
+    /// i.e. no limit.
+    pub max_elements: Option<usize>,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_elements: None,
+        }
+    }
+}
+
+impl DecodeOptions {
+    /// Options that reject non canonical encodings.
+    pub fn strict() -> Self {
+        Self {
+            strict: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Precomputed layout of a list of types, for decoding many inputs of the same
+/// schema, e.g. the call data of a single function.
+///
+/// The structure of each type, i.e. which nested types are dynamic and the head
+/// sizes of the static ones, along with the head offsets and the nesting depth
+/// check, is computed once instead of on every decode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeLayout {
+    params: Vec<LayoutParam>,
+    head_size: usize,
+    opts: DecodeOptions,
+    max_depth_exceeded: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LayoutParam {
+    ty: Type,
+    layout: TypeLayout,
+    head_offset: usize,
+}
+
+// Structure of a type the decoder needs: whether it is dynamic, its head size and
+// the structure of its nested types.
+//
+// Plain decodes compute it from the type as they go, with `FromType`, while
+// `DecodeLayout` reads it from the `TypeLayout` it precomputed.
+trait Structure: Copy {
+    fn is_dynamic(self, ty: &Type) -> bool;
+
+    fn head_size(self, ty: &Type) -> usize;
+
+    // Structure of the element type of arrays, or of the component type at `i` of
+    // tuples.
+    fn component(self, i: usize) -> Self;
+}
+
+#[derive(Clone, Copy)]
+struct FromType;
+
+impl Structure for FromType {
+    fn is_dynamic(self, ty: &Type) -> bool {
+        ty.is_dynamic()
+    }
+
+    fn head_size(self, ty: &Type) -> usize {
+        ty.head_size()
+    }
+
+    fn component(self, _: usize) -> Self {
+        FromType
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TypeLayout {
+    dynamic: bool,
+    head_size: usize,
+    components: Vec<TypeLayout>,
+}
+
+impl TypeLayout {
+    fn of(ty: &Type) -> TypeLayout {
+        let components = match ty {
+            Type::FixedArray(ty, _) | Type::Array(ty) => vec![Self::of(ty)],
+            Type::Tuple(tys) => tys.iter().map(|(_, ty)| Self::of(ty)).collect(),
+            _ => vec![],
+        };
+
+        TypeLayout {
+            dynamic: ty.is_dynamic(),
+            head_size: ty.head_size(),
+            components,
+        }
+    }
+}
+
+impl Structure for &TypeLayout {
+    fn is_dynamic(self, _: &Type) -> bool {
+        self.dynamic
+    }
+
+    fn head_size(self, _: &Type) -> usize {
+        self.head_size
+    }
+
+    fn component(self, i: usize) -> Self {
+        &self.components[i]
+    }
+}
+
+// Decoding options along with the number of values decoded so far, shared by all
+// the values decoded from one input.
+struct DecodeState<'a> {
+    opts: &'a DecodeOptions,
+    elements: usize,
+}
+
+impl<'a> DecodeState<'a> {
+    fn new(opts: &'a DecodeOptions) -> Self {
+        Self { opts, elements: 0 }
+    }
+
+    // Adds `count` values to the number of decoded values, failing if it exceeds
+    // `opts.max_elements`.
+    fn count_elements(&mut self, count: usize) -> Result<()> {
+        self.elements = self.elements.saturating_add(count);
+
+        match self.opts.max_elements {
+            Some(max_elements) if self.elements > max_elements => {
+                Err(AbiError::MaxElementsExceeded { max_elements }.into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl DecodeLayout {
+    /// Precomputes the layout of the given types using the default decoding options.
+    pub fn precompute(tys: &[Type]) -> DecodeLayout {
+        Self::precompute_with(tys, &DecodeOptions::default())
+    }
+
+    /// Precomputes the layout of the given types using the given decoding options.
+    pub fn precompute_with(tys: &[Type], opts: &DecodeOptions) -> DecodeLayout {
+        let mut head_size = 0;
+        let params = tys
+            .iter()
+            .map(|ty| {
+                let param = LayoutParam {
+                    ty: ty.clone(),
+                    layout: TypeLayout::of(ty),
+                    head_offset: head_size,
+                };
+                head_size = head_size.saturating_add(param.layout.head_size);
+
+                param
+            })
+            .collect();
+
+        DecodeLayout {
+            params,
+            head_size,
+            opts: *opts,
+            max_depth_exceeded: tys.iter().any(|ty| ty.depth() > opts.max_depth),
+        }
+    }
+
+    /// Returns the size of the head region, i.e. the minimum size of valid input.
+    pub fn head_size(&self) -> usize {
+        self.head_size
+    }
+
+    /// Returns whether any of the types is dynamic, otherwise all valid inputs are
+    /// exactly [`DecodeLayout::head_size`] bytes long.
+    pub fn is_dynamic(&self) -> bool {
+        self.params.iter().any(|param| param.layout.dynamic)
+    }
+
+    /// Decodes values from bytes like [`Value::decode_from_slice_with`].
+    pub fn decode(&self, bs: &[u8]) -> Result<Vec<Value>> {
+        if self.max_depth_exceeded {
+            return Err(AbiError::MaxDepthExceeded {
+                max_depth: self.opts.max_depth,
+            }
+            .into());
+        }
+
+        if bs.len() < self.head_size {
+            return Err(anyhow!(
+                "input of {} bytes is shorter than its {} bytes head",
+                bs.len(),
+                self.head_size
+            ));
+        }
+
+        let mut state = DecodeState::new(&self.opts);
+        state.count_elements(self.params.len())?;
+
+        let mut values = Vec::with_capacity(self.params.len());
+        for param in &self.params {
+            let (value, _, _) = Value::decode(
+                bs,
+                &param.ty,
+                &param.layout,
+                0,
+                param.head_offset,
+                self.head_size,
+                &mut state,
+            )?;
+
+            values.push(value);
+        }
+
+        Ok(values)
+    }
+}
 
 /// ABI decoded value.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Value {
     /// Unsigned int value (uint<M>).
     Uint(U256, usize),
     /// Signed int value (int<M>).
+    ///
+    /// The canonical form of the word is the value's 256 bits two's complement,
+    /// sign extended from its `M` bits, e.g. `int8(-1)` is `U256::MAX`. Decoding and
+    /// [`Value::int`] always produce it, so equal values compare equal.
     Int(U256, usize),
     /// Address value (address).
     Address(H160),
@@ -31,11 +288,301 @@ pub enum Value {
 }
 
 impl Value {
+    /// Creates a `uint<bits>` value, checking that `bits` is a valid size and that
+    /// the value fits in it.
+    pub fn uint(value: impl Into<U256>, bits: usize) -> Result<Value> {
+        if !check_int_size(&bits) {
+            return Err(anyhow!("invalid uint size: {}", bits));
+        }
+
+        let value = value.into();
+
+        if value.bits() > bits {
+            return Err(anyhow!("value {} does not fit in uint{}", value, bits));
+        }
+
+        Ok(Value::Uint(value, bits))
+    }
+
+    /// Creates an `int<bits>` value, checking that `bits` is a valid size and that
+    /// the value fits in it.
+    ///
+    /// The value is stored as its sign extended 256 bits two's complement form. Use
+    /// [`Value::int_from_word`] for `int<bits>` values outside the `i128` range.
+    pub fn int(value: i128, bits: usize) -> Result<Value> {
+        if !check_int_size(&bits) {
+            return Err(anyhow!("invalid int size: {}", bits));
+        }
+
+        if bits < 128 {
+            let bound = 1i128 << (bits - 1);
+
+            if value < -bound || value >= bound {
+                return Err(anyhow!("value {} does not fit in int{}", value, bits));
+            }
+        }
+
+        let word = if value < 0 {
+            !U256::from(!value as u128)
+        } else {
+            U256::from(value as u128)
+        };
+
+        Ok(Value::Int(word, bits))
+    }
+
+    /// Creates an `int<bits>` value from its 256 bits two's complement form, checking
+    /// that `bits` is a valid size and that the word is sign extended from it, i.e.
+    /// that the value fits in `bits`.
+    pub fn int_from_word(word: U256, bits: usize) -> Result<Value> {
+        if !check_int_size(&bits) {
+            return Err(anyhow!("invalid int size: {}", bits));
+        }
+
+        if !Self::is_sign_extended(word, bits) {
+            return Err(anyhow!("value 0x{:x} does not fit in int{}", word, bits));
+        }
+
+        Ok(Value::Int(word, bits))
+    }
+
+    /// Creates an address value.
+    pub fn address(address: H160) -> Value {
+        Value::Address(address)
+    }
+
+    /// Creates a `bytes<size>` value, checking that `size` is a valid size and that
+    /// the given bytes have that length.
+    pub fn fixed_bytes(bytes: impl Into<Vec<u8>>, size: usize) -> Result<Value> {
+        if !check_fixed_bytes_size(&size) {
+            return Err(anyhow!("invalid fixed bytes size: {}", size));
+        }
+
+        let bytes = bytes.into();
+
+        if bytes.len() != size {
+            return Err(anyhow!(
+                "expected {} bytes for bytes{}, got {}",
+                size,
+                size,
+                bytes.len()
+            ));
+        }
+
+        Ok(Value::FixedBytes(bytes))
+    }
+
+    /// Creates a `T[k]` value, checking that all values have the element type `ty`.
+    pub fn fixed_array(values: Vec<Value>, ty: Type) -> Result<Value> {
+        Self::check_elements(&values, &ty)?;
+
+        Ok(Value::FixedArray(values, ty))
+    }
+
+    /// Creates a `T[]` value, checking that all values have the element type `ty`.
+    pub fn array(values: Vec<Value>, ty: Type) -> Result<Value> {
+        Self::check_elements(&values, &ty)?;
+
+        Ok(Value::Array(values, ty))
+    }
+
+    /// Parses a value of the given type from a literal, e.g. a command line argument.
+    ///
+    /// Accepts decimal or `0x` prefixed hex integers (with a leading `-` for negative
+    /// signed ones), `0x` prefixed hex addresses and bytes, `true` and `false`,
+    /// strings (optionally double quoted, with `\"` and `\\` escapes), `[a, b, c]`
+    /// array literals and `(a, b)` tuple literals.
+    ///
+    /// ```
+    /// use ethereum_abi::{Type, Value};
+    /// use ethereum_types::U256;
+    ///
+    /// let value = Value::parse("[1, 0x02]", &"uint8[]".parse().unwrap()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     value,
+    ///     Value::Array(
+    ///         vec![Value::Uint(U256::from(1), 8), Value::Uint(U256::from(2), 8)],
+    ///         Type::Uint(8),
+    ///     )
+    /// );
+    /// ```
+    pub fn parse(s: &str, ty: &Type) -> Result<Value> {
+        let s = s.trim();
+
+        match ty {
+            Type::Uint(size) => Self::uint(Self::parse_uint_literal(s)?, *size),
+
+            Type::Int(size) => {
+                let (negative, digits) = match s.strip_prefix('-') {
+                    Some(digits) => (true, digits),
+                    None => (false, s),
+                };
+                let magnitude = Self::parse_uint_literal(digits)?;
+
+                if !check_int_size(size) {
+                    return Err(anyhow!("invalid int size: {}", size));
+                }
+
+                // the magnitude of the smallest value, -2^(size - 1)
+                let bound = U256::one() << (*size - 1);
+
+                if magnitude > bound || (magnitude == bound && !negative) {
+                    return Err(anyhow!("value {} does not fit in int{}", s, size));
+                }
+
+                let word = if negative {
+                    (!magnitude).overflowing_add(U256::one()).0
+                } else {
+                    magnitude
+                };
+
+                Ok(Value::Int(word, *size))
+            }
+
+            Type::Address => {
+                let bytes = crate::hex_to_bytes(s)?;
+
+                if bytes.len() != 20 {
+                    return Err(anyhow!("invalid address: {}", s));
+                }
+
+                Ok(Value::Address(H160::from_slice(&bytes)))
+            }
+
+            Type::Bool => match s {
+                "true" => Ok(Value::Bool(true)),
+                "false" => Ok(Value::Bool(false)),
+                _ => Err(anyhow!("invalid bool: {}", s)),
+            },
+
+            Type::String => Ok(Value::String(Self::parse_string_literal(s)?)),
+
+            Type::FixedBytes(size) => Self::fixed_bytes(crate::hex_to_bytes(s)?, *size),
+
+            Type::Bytes => Ok(Value::Bytes(crate::hex_to_bytes(s)?)),
+
+            Type::FixedArray(elem_ty, size) => {
+                let values = Self::parse_list_literal(s, '[', ']', elem_ty)?;
+
+                if values.len() != *size {
+                    return Err(anyhow!(
+                        "expected {} elements for {}, got {}",
+                        size,
+                        ty,
+                        values.len()
+                    ));
+                }
+
+                Ok(Value::FixedArray(values, *elem_ty.clone()))
+            }
+
+            Type::Array(elem_ty) => Ok(Value::Array(
+                Self::parse_list_literal(s, '[', ']', elem_ty)?,
+                *elem_ty.clone(),
+            )),
+
+            Type::Tuple(tys) => {
+                let items = Self::split_list_literal(s, '(', ')')?;
+
+                if items.len() != tys.len() {
+                    return Err(anyhow!(
+                        "expected {} components for {}, got {}",
+                        tys.len(),
+                        ty,
+                        items.len()
+                    ));
+                }
+
+                items
+                    .into_iter()
+                    .zip(tys)
+                    .map(|(item, (name, ty))| Ok((name.clone(), Self::parse(item, ty)?)))
+                    .collect::<Result<_>>()
+                    .map(Value::Tuple)
+            }
+        }
+    }
+
     /// Decodes values from bytes using the given type hint.
+    ///
+    /// Decoding is lenient, i.e. padding bytes are not checked.
+    ///
+    /// ```
+    /// use ethereum_abi::{Type, Value};
+    /// use ethereum_types::U256;
+    ///
+    /// let bs = Value::encode(&[Value::Uint(U256::from(1), 256), Value::Bool(true)]);
+    ///
+    /// let values = Value::decode_from_slice(&bs, &[Type::Uint(256), Type::Bool]).unwrap();
+    ///
+    /// assert_eq!(values, vec![Value::Uint(U256::from(1), 256), Value::Bool(true)]);
+    /// ```
     pub fn decode_from_slice(bs: &[u8], tys: &[Type]) -> Result<Vec<Value>> {
+        Self::decode_from_slice_with(bs, tys, &DecodeOptions::default())
+    }
+
+    /// Decodes values from bytes using the given type hint and decoding options.
+    ///
+    /// Types nested deeper than `opts.max_depth` are rejected with
+    /// [`AbiError::MaxDepthExceeded`], and input holding more than `opts.max_elements`
+    /// values with [`AbiError::MaxElementsExceeded`].
+    pub fn decode_from_slice_with(
+        bs: &[u8],
+        tys: &[Type],
+        opts: &DecodeOptions,
+    ) -> Result<Vec<Value>> {
+        Self::decode_from_slice_at_with(bs, tys, 0, opts)
+    }
+
+    /// Decodes values encoded at the given position of a larger buffer, without
+    /// copying them out of it.
+    ///
+    /// Offsets in the encoding are relative to `base`, e.g. function arguments
+    /// can be decoded from call data in place by skipping its 4 bytes selector:
+    ///
+    /// ```
+    /// use ethereum_abi::{Type, Value};
+    ///
+    /// let mut call_data = vec![0xa9, 0x05, 0x9c, 0xbb];
+    /// call_data.extend(Value::encode(&[Value::String("abc".to_string())]));
+    ///
+    /// let args = Value::decode_from_slice_at(&call_data, &[Type::String], 4).unwrap();
+    ///
+    /// assert_eq!(args, vec![Value::String("abc".to_string())]);
+    /// ```
+    pub fn decode_from_slice_at(bs: &[u8], tys: &[Type], base: usize) -> Result<Vec<Value>> {
+        Self::decode_from_slice_at_with(bs, tys, base, &DecodeOptions::default())
+    }
+
+    /// Decodes values encoded at the given position of a larger buffer using the
+    /// given decoding options.
+    pub fn decode_from_slice_at_with(
+        bs: &[u8],
+        tys: &[Type],
+        base: usize,
+        opts: &DecodeOptions,
+    ) -> Result<Vec<Value>> {
+        if base > bs.len() {
+            return Err(anyhow!("base position {} is past the end of input", base));
+        }
+
+        if tys.iter().any(|ty| ty.depth() > opts.max_depth) {
+            return Err(AbiError::MaxDepthExceeded {
+                max_depth: opts.max_depth,
+            }
+            .into());
+        }
+
+        let mut state = DecodeState::new(opts);
+        state.count_elements(tys.len())?;
+
+        let head_size = Self::strict_head_size(tys.iter().map(Type::head_size), opts);
+
         tys.iter()
             .try_fold((vec![], 0), |(mut values, at), ty| {
-                let (value, consumed) = Self::decode(bs, ty, 0, at)?;
+                let (value, consumed, _) =
+                    Self::decode(bs, ty, FromType, base, at, head_size, &mut state)?;
                 values.push(value);
 
                 Ok((values, at + consumed))
@@ -43,10 +590,68 @@ impl Value {
             .map(|(values, _)| values)
     }
 
+    /// Decodes values from bytes like [`Value::decode_from_slice`], along with the
+    /// input byte range each value was read from.
+    ///
+    /// Static values span their words in the head region. Dynamic values span their
+    /// tail data, from where their head offset points to the furthest byte read while
+    /// decoding them, including the padding of their last word when the input holds
+    /// it.
+    pub fn decode_from_slice_with_ranges(
+        bs: &[u8],
+        tys: &[Type],
+    ) -> Result<Vec<(Value, Range<usize>)>> {
+        let opts = DecodeOptions::default();
+
+        if tys.iter().any(|ty| ty.depth() > opts.max_depth) {
+            return Err(AbiError::MaxDepthExceeded {
+                max_depth: opts.max_depth,
+            }
+            .into());
+        }
+
+        let mut state = DecodeState::new(&opts);
+        let head_size = Self::strict_head_size(tys.iter().map(Type::head_size), &opts);
+
+        tys.iter()
+            .try_fold((vec![], 0), |(mut values, at), ty| {
+                let (value, consumed, range) =
+                    Self::decode(bs, ty, FromType, 0, at, head_size, &mut state)?;
+
+                values.push((value, range));
+
+                Ok((values, at + consumed))
+            })
+            .map(|(values, _)| values)
+    }
+
     /// Encodes values into bytes.
     pub fn encode(values: &[Self]) -> Vec<u8> {
-        let mut buf = vec![];
-        let mut alloc_queue = std::collections::VecDeque::new();
+        let mut buf = Vec::with_capacity(Self::encoded_size(values));
+        Self::encode_into(values, &mut buf);
+
+        buf
+    }
+
+    /// Encodes values into a `0x` prefixed hex string, e.g. for JSON-RPC call data
+    /// fields.
+    pub fn encode_to_hex(values: &[Self]) -> String {
+        format!("0x{}", hex::encode(Self::encode(values)))
+    }
+
+    /// Encodes values, appending them to the given buffer.
+    ///
+    /// Offsets are relative to the start of the appended encoding, so it's valid on
+    /// its own when sliced out of the buffer. Buffers can be cleared and reused to
+    /// avoid allocating on every encoding.
+    pub fn encode_into(values: &[Self], buf: &mut Vec<u8>) {
+        Self::encode_values(&mut values.iter(), buf)
+    }
+
+    // Encodes a sequence of values, appending them to the given buffer.
+    fn encode_values(values: &mut dyn Iterator<Item = &Value>, buf: &mut Vec<u8>) {
+        let start = buf.len();
+        let mut alloc_queue = VecDeque::new();
 
         for value in values {
             match value {
@@ -82,23 +687,20 @@ impl Value {
                 }
 
                 Value::FixedArray(values, _) => {
-                    if value.type_of().is_dynamic() {
+                    if value.is_dynamic() {
                         alloc_queue.push_back((buf.len(), value));
                         buf.resize(buf.len() + 32, 0);
                     } else {
-                        buf.extend(Self::encode(values));
+                        Self::encode_values(&mut values.iter(), buf);
                     }
                 }
 
                 Value::Tuple(values) => {
-                    if value.type_of().is_dynamic() {
+                    if value.is_dynamic() {
                         alloc_queue.push_back((buf.len(), value));
                         buf.resize(buf.len() + 32, 0);
                     } else {
-                        let values: Vec<_> =
-                            values.iter().cloned().map(|(_, value)| value).collect();
-
-                        buf.extend(Self::encode(&values));
+                        Self::encode_values(&mut values.iter().map(|(_, value)| value), buf);
                     }
                 }
 
@@ -109,58 +711,49 @@ impl Value {
             };
         }
 
-        let mut alloc_offset = buf.len();
-
         while let Some((at, value)) = alloc_queue.pop_front() {
-            U256::from(alloc_offset).to_big_endian(&mut buf[at..(at + 32)]);
+            // offsets are relative to the start of this encoding
+            U256::from(buf.len() - start).to_big_endian(&mut buf[at..(at + 32)]);
 
             match value {
-                Value::String(s) => {
-                    alloc_offset = Self::encode_bytes(&mut buf, s.as_bytes(), alloc_offset);
-                }
+                Value::String(s) => Self::encode_bytes(buf, s.as_bytes()),
 
-                Value::Bytes(bytes) => {
-                    alloc_offset = Self::encode_bytes(&mut buf, bytes, alloc_offset);
-                }
+                Value::Bytes(bytes) => Self::encode_bytes(buf, bytes),
 
                 Value::Array(values, _) => {
-                    buf.resize(buf.len() + 32, 0);
-
                     // write array length
-                    U256::from(values.len())
-                        .to_big_endian(&mut buf[alloc_offset..(alloc_offset + 32)]);
-                    alloc_offset += 32;
+                    let len_at = buf.len();
+                    buf.resize(buf.len() + 32, 0);
+                    U256::from(values.len()).to_big_endian(&mut buf[len_at..(len_at + 32)]);
 
                     // write array values
-                    let bytes = Self::encode(values);
-                    alloc_offset += bytes.len();
-                    buf.extend(bytes);
+                    Self::encode_values(&mut values.iter(), buf);
                 }
 
-                Value::FixedArray(values, _) => {
-                    // write array values
-                    let bytes = Self::encode(values);
-                    alloc_offset += bytes.len();
-                    buf.extend(bytes);
-                }
+                // write array values
+                Value::FixedArray(values, _) => Self::encode_values(&mut values.iter(), buf),
 
+                // write tuple values
                 Value::Tuple(values) => {
-                    // write tuple values
-                    let values: Vec<_> = values.iter().cloned().map(|(_, value)| value).collect();
-
-                    let bytes = Self::encode(&values);
-                    alloc_offset += bytes.len();
-                    buf.extend(bytes);
+                    Self::encode_values(&mut values.iter().map(|(_, value)| value), buf)
                 }
 
                 _ => panic!("value of fixed size type {:?} in dynamic alloc area", value),
             };
         }
+    }
 
-        buf
+    /// Returns the number of bytes [`Value::encode`] produces for the given values,
+    /// without encoding them.
+    pub fn encoded_size(values: &[Self]) -> usize {
+        values.iter().map(Self::value_encoded_size).sum()
     }
 
     /// Returns the type of the given value.
+    ///
+    /// `Value::FixedBytes` values map to `bytes<M>` where `M` is their length, while
+    /// `Value::Bytes` values always map to `bytes`. Arrays carry their element type,
+    /// so the type of empty arrays is recovered as well.
     pub fn type_of(&self) -> Type {
         match self {
             Value::Uint(_, size) => Type::Uint(*size),
@@ -181,7 +774,123 @@ impl Value {
         }
     }
 
-    fn decode(bs: &[u8], ty: &Type, base_addr: usize, at: usize) -> Result<(Value, usize)> {
+    /// Returns the zero value of the given type.
+    ///
+    /// That is `0` for ints, the zero address, `false`, `M` zero bytes for `bytes<M>`,
+    /// empty strings, bytes and arrays, and fixed arrays and tuples of the zero values
+    /// of their elements.
+    pub fn default_for(ty: &Type) -> Value {
+        match ty {
+            Type::Uint(size) => Value::Uint(U256::zero(), *size),
+            Type::Int(size) => Value::Int(U256::zero(), *size),
+            Type::Address => Value::Address(H160::zero()),
+            Type::Bool => Value::Bool(false),
+            Type::FixedBytes(size) => Value::FixedBytes(vec![0; *size]),
+            Type::FixedArray(ty, size) => {
+                Value::FixedArray(vec![Self::default_for(ty); *size], (**ty).clone())
+            }
+            Type::String => Value::String(String::new()),
+            Type::Bytes => Value::Bytes(vec![]),
+            Type::Array(ty) => Value::Array(vec![], (**ty).clone()),
+            Type::Tuple(tys) => Value::Tuple(
+                tys.iter()
+                    .map(|(name, ty)| (name.clone(), Self::default_for(ty)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the 256 bits two's complement form of an `int<M>` value, sign extended
+    /// from its `M` bits, or `None` for other values.
+    ///
+    /// Decoded int values are always stored in this form, but values built by hand may
+    /// hold arbitrary bits above their size.
+    pub fn as_sign_extended(&self) -> Option<U256> {
+        match self {
+            Value::Int(word, size) => Some(Self::sign_extend(*word, *size)),
+            _ => None,
+        }
+    }
+
+    /// Compares values numerically, ignoring the declared bit sizes of ints and the
+    /// element types of arrays.
+    ///
+    /// Int values are compared by their sign extended form, so `Int(-1, 8)` equals
+    /// `Int(-1, 256)`. Otherwise values must match like with `==`, e.g. uint and int
+    /// values are never equal. The derived `PartialEq` still compares sizes.
+    pub fn value_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Uint(a, _), Value::Uint(b, _)) => a == b,
+            (Value::Int(_, _), Value::Int(_, _)) => {
+                self.as_sign_extended() == other.as_sign_extended()
+            }
+            (Value::FixedArray(a, _), Value::FixedArray(b, _))
+            | (Value::Array(a, _), Value::Array(b, _)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.value_eq(b))
+            }
+            (Value::Tuple(a), Value::Tuple(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b)
+                        .all(|((a_name, a), (b_name, b))| a_name == b_name && a.value_eq(b))
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Converts the value into JSON.
+    ///
+    /// Integers are represented as decimal strings so no precision is lost, with
+    /// signed integers interpreted as two's complement of their declared size.
+    /// Addresses and bytes are `0x` prefixed hex strings, and arrays are JSON
+    /// arrays. Tuples are JSON objects keyed by component name, or arrays if any
+    /// component is unnamed.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value as Json;
+
+        match self {
+            Value::Uint(i, _) => Json::String(i.to_string()),
+            Value::Int(i, size) => Json::String(Self::int_to_string(*i, *size)),
+            Value::Address(addr) => Json::String(format!("{:?}", addr)),
+            Value::Bool(b) => Json::Bool(*b),
+            Value::FixedBytes(bytes) | Value::Bytes(bytes) => {
+                Json::String(format!("0x{}", hex::encode(bytes)))
+            }
+            Value::String(s) => Json::String(s.clone()),
+            Value::FixedArray(values, _) | Value::Array(values, _) => {
+                Json::Array(values.iter().map(Value::to_json).collect())
+            }
+            Value::Tuple(values) => {
+                if values.iter().any(|(name, _)| name.is_empty()) {
+                    Json::Array(values.iter().map(|(_, value)| value.to_json()).collect())
+                } else {
+                    Json::Object(
+                        values
+                            .iter()
+                            .map(|(name, value)| (name.clone(), value.to_json()))
+                            .collect(),
+                    )
+                }
+            }
+        }
+    }
+
+    // Decodes a value of the given type and structure, counting the values nested in
+    // it in `state`. `head_size` is the size of the head region of the values sharing
+    // `base_addr`, which offsets must point past in strict mode.
+    //
+    // Returns the value, the number of head bytes it takes and the input range it was
+    // read from: its head words for static values, or its tail up to the furthest
+    // byte read for dynamic ones.
+    fn decode<S: Structure>(
+        bs: &[u8],
+        ty: &Type,
+        structure: S,
+        base_addr: usize,
+        at: usize,
+        head_size: usize,
+        state: &mut DecodeState,
+    ) -> Result<(Value, usize, Range<usize>)> {
         match ty {
             Type::Uint(size) => {
                 let at = base_addr + at;
@@ -191,7 +900,11 @@ impl Value {
 
                 let uint = U256::from_big_endian(slice);
 
-                Ok((Value::Uint(uint, *size), 32))
+                if state.opts.strict && *size < 256 && (uint >> *size) != U256::zero() {
+                    return Err(anyhow!("non canonical encoding of uint{}", size));
+                }
+
+                Ok((Value::Uint(uint, *size), 32, at..(at + 32)))
             }
 
             Type::Int(size) => {
@@ -202,19 +915,31 @@ impl Value {
 
                 let uint = U256::from_big_endian(slice);
 
-                Ok((Value::Int(uint, *size), 32))
+                if state.opts.strict && !Self::is_sign_extended(uint, *size) {
+                    return Err(anyhow!("non canonical encoding of int{}", size));
+                }
+
+                Ok((
+                    Value::Int(Self::sign_extend(uint, *size), *size),
+                    32,
+                    at..(at + 32),
+                ))
             }
 
             Type::Address => {
                 let at = base_addr + at;
                 let slice = bs
-                    .get((at + 12)..(at + 32))
+                    .get(at..(at + 32))
                     .ok_or_else(|| anyhow!("reached end of input while decoding address"))?;
 
+                if state.opts.strict && slice[..12].iter().any(|b| *b != 0) {
+                    return Err(anyhow!("non canonical encoding of address"));
+                }
+
                 // big-endian, same as if it were a uint160.
-                let addr = H160::from_slice(slice);
+                let addr = H160::from_slice(&slice[12..]);
 
-                Ok((Value::Address(addr), 32))
+                Ok((Value::Address(addr), 32, at..(at + 32)))
             }
 
             Type::Bool => {
@@ -223,9 +948,15 @@ impl Value {
                     .get(at..(at + 32))
                     .ok_or_else(|| anyhow!("reached end of input while decoding bool"))?;
 
-                let b = U256::from_big_endian(slice) == U256::one();
+                let word = U256::from_big_endian(slice);
+
+                if state.opts.strict && word > U256::one() {
+                    return Err(anyhow!("non canonical encoding of bool"));
+                }
+
+                let b = word == U256::one();
 
-                Ok((Value::Bool(b), 32))
+                Ok((Value::Bool(b), 32, at..(at + 32)))
             }
 
             Type::FixedBytes(size) => {
@@ -235,79 +966,128 @@ impl Value {
                     .ok_or_else(|| anyhow!("reached end of input while decoding bytes{}", size))?
                     .to_vec();
 
-                Ok((Value::FixedBytes(bv), Self::padded32_size(*size)))
+                if state.opts.strict {
+                    let padding = bs
+                        .get((at + size)..(at + Self::padded32_size(*size)))
+                        .ok_or_else(|| {
+                            anyhow!("reached end of input while decoding bytes{}", size)
+                        })?;
+
+                    if padding.iter().any(|b| *b != 0) {
+                        return Err(anyhow!("non canonical encoding of bytes{}", size));
+                    }
+                }
+
+                let consumed = Self::padded32_size(*size);
+
+                Ok((Value::FixedBytes(bv), consumed, at..(at + consumed)))
             }
 
-            Type::FixedArray(ty, size) => {
-                let (base_addr, at) = if ty.is_dynamic() {
-                    // For fixed arrays of types that are dynamic, we just jump
-                    // to the offset location and decode from there.
-                    let slice = bs.get(at..(at + 32)).ok_or_else(|| {
-                        anyhow!("reached end of input while decoding {}[{}]", ty, size)
-                    })?;
-                    let offset = U256::from_big_endian(slice).as_usize();
-
-                    (base_addr + offset, 0)
-                } else {
-                    // There's no need to change the addressing because fixed arrays
-                    // will consume input by calling decode recursively and addressing
-                    // will be computed correctly inside those calls.
-                    (base_addr, at)
-                };
+            Type::FixedArray(elem_ty, size) if !structure.is_dynamic(ty) => {
+                let structure = structure.component(0);
+                state.count_elements(*size)?;
+
+                // Static elements are stored one after the other in place, so they
+                // are read sequentially without any offset handling.
+                let mut values =
+                    Vec::with_capacity(Self::static_capacity(bs, base_addr + at, *size));
+                let mut consumed = 0;
+
+                for _ in 0..*size {
+                    let (value, value_consumed, _) = Self::decode(
+                        bs,
+                        elem_ty,
+                        structure,
+                        base_addr,
+                        at + consumed,
+                        head_size,
+                        state,
+                    )?;
+
+                    values.push(value);
+                    consumed += value_consumed;
+                }
 
-                (0..(*size))
-                    .try_fold((vec![], 0), |(mut values, total_consumed), _| {
-                        let (value, consumed) =
-                            Self::decode(bs, ty, base_addr, at + total_consumed)?;
+                let start = base_addr + at;
 
-                        values.push(value);
+                Ok((
+                    Value::FixedArray(values, *elem_ty.clone()),
+                    consumed,
+                    start..(start + consumed),
+                ))
+            }
 
-                        Ok((values, total_consumed + consumed))
-                    })
-                    .map(|(values, consumed)| {
-                        let consumed = if ty.is_dynamic() { 32 } else { consumed };
+            Type::FixedArray(ty, size) => {
+                // For fixed arrays of types that are dynamic, we just jump
+                // to the offset location and decode from there.
+                let head_at = base_addr + at;
+                let slice = bs.get(head_at..(head_at + 32)).ok_or_else(|| {
+                    anyhow!("reached end of input while decoding {}[{}]", ty, size)
+                })?;
+                let offset = Self::to_usize(U256::from_big_endian(slice))?;
+                let base_addr = Self::jump_to_tail(bs, base_addr, offset, head_size, state.opts)?;
 
-                        (Value::FixedArray(values, *ty.clone()), consumed)
+                state.count_elements(*size)?;
+
+                let structure = structure.component(0);
+                let head_size = size.saturating_mul(structure.head_size(ty));
+
+                (0..(*size))
+                    .try_fold(
+                        (vec![], 0, base_addr),
+                        |(mut values, total_consumed, end), _| {
+                            let (value, consumed, span) = Self::decode(
+                                bs,
+                                ty,
+                                structure,
+                                base_addr,
+                                total_consumed,
+                                head_size,
+                                state,
+                            )?;
+
+                            values.push(value);
+
+                            let total_consumed = total_consumed + consumed;
+                            let end = end.max(base_addr + total_consumed).max(span.end);
+
+                            Ok((values, total_consumed, end))
+                        },
+                    )
+                    // consumes only the offset pointer
+                    .map(|(values, _, end)| {
+                        (Value::FixedArray(values, *ty.clone()), 32, base_addr..end)
                     })
             }
 
             Type::String => {
-                let (bytes_value, consumed) = Self::decode(bs, &Type::Bytes, base_addr, at)?;
-
-                let bytes = if let Value::Bytes(bytes) = bytes_value {
-                    bytes
-                } else {
-                    // should always be Value::Bytes
-                    unreachable!();
+                let (bytes, data_at) =
+                    Self::decode_bytes_slice(bs, base_addr, at, head_size, state.opts)?;
+
+                let s = match core::str::from_utf8(bytes) {
+                    Ok(s) => s.to_string(),
+                    Err(err) if state.opts.strict => {
+                        return Err(AbiError::InvalidUtf8 {
+                            offset: data_at + err.valid_up_to(),
+                        }
+                        .into())
+                    }
+                    Err(_) => String::from_utf8_lossy(bytes).into_owned(),
                 };
 
-                let s = String::from_utf8(bytes)?;
+                let span = Self::bytes_span(bs, data_at, bytes.len());
 
-                Ok((Value::String(s), consumed))
+                // consumes only the first 32 bytes, i.e. the offset pointer
+                Ok((Value::String(s), 32, span))
             }
 
             Type::Bytes => {
-                let at = base_addr + at;
-                let slice = bs
-                    .get(at..(at + 32))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding bytes offset"))?;
-                let offset = U256::from_big_endian(slice).as_usize();
-
-                let at = base_addr + offset;
-
-                let slice = bs
-                    .get(at..(at + 32))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding bytes length"))?;
-                let bytes_len = U256::from_big_endian(slice).as_usize();
-
-                let at = at + 32;
-                let bytes = bs
-                    .get(at..(at + bytes_len))
-                    .ok_or_else(|| anyhow!("reached end of input while decoding bytes"))?
-                    .to_vec();
+                let (bytes, data_at) =
+                    Self::decode_bytes_slice(bs, base_addr, at, head_size, state.opts)?;
+                let span = Self::bytes_span(bs, data_at, bytes.len());
 
                 // consumes only the first 32 bytes, i.e. the offset pointer
-                Ok((Value::Bytes(bytes), 32))
+                Ok((Value::Bytes(bytes.to_vec()), 32, span))
             }
 
             Type::Array(ty) => {
@@ -315,71 +1095,392 @@ impl Value {
                 let slice = bs
                     .get(at..(at + 32))
                     .ok_or_else(|| anyhow!("reached end of input while decoding array offset"))?;
-                let offset = U256::from_big_endian(slice).as_usize();
+                let offset = Self::to_usize(U256::from_big_endian(slice))?;
 
-                let at = base_addr + offset;
+                let start = Self::jump_to_tail(bs, base_addr, offset, head_size, state.opts)?;
                 let slice = bs
-                    .get(at..(at + 32))
+                    .get(start..(start + 32))
                     .ok_or_else(|| anyhow!("reached end of input while decoding array length"))?;
-                let array_len = U256::from_big_endian(slice).as_usize();
+                let array_len = Self::to_usize(U256::from_big_endian(slice))?;
 
-                let at = at + 32;
+                state.count_elements(array_len)?;
+
+                let at = start + 32;
+                let structure = structure.component(0);
+                let head_size = array_len.saturating_mul(structure.head_size(ty));
 
                 (0..array_len)
-                    .try_fold((vec![], 0), |(mut values, total_consumed), _| {
-                        let (value, consumed) = Self::decode(bs, ty, at, total_consumed)?;
+                    .try_fold((vec![], 0, at), |(mut values, total_consumed, end), _| {
+                        let (value, consumed, span) =
+                            Self::decode(bs, ty, structure, at, total_consumed, head_size, state)?;
 
                         values.push(value);
 
-                        Ok((values, total_consumed + consumed))
+                        let total_consumed = total_consumed + consumed;
+                        let end = end.max(at + total_consumed).max(span.end);
+
+                        Ok((values, total_consumed, end))
                     })
-                    .map(|(values, _)| (Value::Array(values, *ty.clone()), 32))
+                    .map(|(values, _, end)| (Value::Array(values, *ty.clone()), 32, start..end))
+            }
+
+            Type::Tuple(tys) if !structure.is_dynamic(ty) => {
+                state.count_elements(tys.len())?;
+
+                // Static tuples are read sequentially, like static fixed arrays.
+                let mut values =
+                    Vec::with_capacity(Self::static_capacity(bs, base_addr + at, tys.len()));
+                let mut consumed = 0;
+
+                for (i, (name, ty)) in tys.iter().enumerate() {
+                    let (value, value_consumed, _) = Self::decode(
+                        bs,
+                        ty,
+                        structure.component(i),
+                        base_addr,
+                        at + consumed,
+                        head_size,
+                        state,
+                    )?;
+
+                    values.push((name.clone(), value));
+                    consumed += value_consumed;
+                }
+
+                let start = base_addr + at;
+
+                Ok((Value::Tuple(values), consumed, start..(start + consumed)))
             }
 
             Type::Tuple(tys) => {
-                // Tuples follow the same logic as fixed arrays.
-                let (base_addr, at) = if ty.is_dynamic() {
-                    let slice = bs.get(at..(at + 32)).ok_or_else(|| {
-                        anyhow!("reached end of input while decoding tuple offset")
-                    })?;
-                    let offset = U256::from_big_endian(slice).as_usize();
-
-                    (base_addr + offset, 0)
-                } else {
-                    (base_addr, at)
-                };
+                // Dynamic tuples follow the same logic as fixed arrays.
+                let head_at = base_addr + at;
+                let slice = bs
+                    .get(head_at..(head_at + 32))
+                    .ok_or_else(|| anyhow!("reached end of input while decoding tuple offset"))?;
+                let offset = Self::to_usize(U256::from_big_endian(slice))?;
+                let base_addr = Self::jump_to_tail(bs, base_addr, offset, head_size, state.opts)?;
+
+                state.count_elements(tys.len())?;
+
+                let head_size = Self::strict_head_size(
+                    tys.iter()
+                        .enumerate()
+                        .map(|(i, (_, ty))| structure.component(i).head_size(ty)),
+                    state.opts,
+                );
 
                 tys.iter()
-                    .cloned()
-                    .try_fold((vec![], 0), |(mut values, total_consumed), (name, ty)| {
-                        let (value, consumed) =
-                            Self::decode(bs, &ty, base_addr, at + total_consumed)?;
+                    .enumerate()
+                    .try_fold(
+                        (vec![], 0, base_addr),
+                        |(mut values, total_consumed, end), (i, (name, ty))| {
+                            let (value, consumed, span) = Self::decode(
+                                bs,
+                                ty,
+                                structure.component(i),
+                                base_addr,
+                                total_consumed,
+                                head_size,
+                                state,
+                            )?;
+
+                            values.push((name.clone(), value));
+
+                            let total_consumed = total_consumed + consumed;
+                            let end = end.max(base_addr + total_consumed).max(span.end);
+
+                            Ok((values, total_consumed, end))
+                        },
+                    )
+                    // consumes only the offset pointer
+                    .map(|(values, _, end)| (Value::Tuple(values), 32, base_addr..end))
+            }
+        }
+    }
 
-                        values.push((name, value));
+    // Decodes the contents of a dynamic bytes value whose offset pointer is
+    // at the given address, returning them along with their input position.
+    fn decode_bytes_slice<'a>(
+        bs: &'a [u8],
+        base_addr: usize,
+        at: usize,
+        head_size: usize,
+        opts: &DecodeOptions,
+    ) -> Result<(&'a [u8], usize)> {
+        let at = base_addr + at;
+        let slice = bs
+            .get(at..(at + 32))
+            .ok_or_else(|| anyhow!("reached end of input while decoding bytes offset"))?;
+        let offset = Self::to_usize(U256::from_big_endian(slice))?;
+
+        let at = Self::jump_to_tail(bs, base_addr, offset, head_size, opts)?;
+
+        let slice = bs
+            .get(at..(at + 32))
+            .ok_or_else(|| anyhow!("reached end of input while decoding bytes length"))?;
+        let bytes_len = Self::to_usize(U256::from_big_endian(slice))?;
+
+        let at = at + 32;
+        let bytes = bs
+            .get(at..at.saturating_add(bytes_len))
+            .ok_or_else(|| anyhow!("reached end of input while decoding bytes"))?;
+
+        Ok((bytes, at))
+    }
 
-                        Ok((values, total_consumed + consumed))
-                    })
-                    .map(|(values, consumed)| {
-                        let consumed = if ty.is_dynamic() { 32 } else { consumed };
+    // Input range of a dynamic bytes value's tail read from the given data position:
+    // its length word, its data and as much of the last word's padding as the input
+    // holds.
+    fn bytes_span(bs: &[u8], data_at: usize, len: usize) -> Range<usize> {
+        let end = (data_at + Self::padded32_size(len)).min(bs.len());
 
-                        (Value::Tuple(values), consumed)
-                    })
+        (data_at - 32)..end
+    }
+
+    // Number of elements worth preallocating for a sequence of static values
+    // starting at the given position. Each element takes at least a word of input,
+    // except for empty tuples, so this bounds allocations for bogus sizes.
+    fn static_capacity(bs: &[u8], at: usize, len: usize) -> usize {
+        len.min(bs.len().saturating_sub(at) / 32 + 1)
+    }
+
+    // Converts a decoded offset or length word to usize, failing instead of
+    // panicking when it does not fit (e.g. on 32-bit targets).
+    fn to_usize(word: U256) -> Result<usize> {
+        if word > U256::from(usize::MAX) {
+            return Err(AbiError::OffsetTooLarge { value: word }.into());
+        }
+
+        Ok(word.as_usize())
+    }
+
+    // Adds a decoded offset to a base address, failing if the resulting
+    // position overflows or lies past the end of the input.
+    fn jump(bs: &[u8], base_addr: usize, offset: usize) -> Result<usize> {
+        base_addr
+            .checked_add(offset)
+            .filter(|at| *at <= bs.len())
+            .ok_or_else(|| anyhow!("offset {} points past the end of input", offset))
+    }
+
+    // Returns the size of the head region made of values with the given head sizes,
+    // which only the strict offset check in `jump_to_tail` needs, so it is 0 in
+    // lenient mode.
+    fn strict_head_size(head_sizes: impl Iterator<Item = usize>, opts: &DecodeOptions) -> usize {
+        if !opts.strict {
+            return 0;
+        }
+
+        head_sizes.fold(0, |acc, head_size| acc.saturating_add(head_size))
+    }
+
+    // Like `jump`, but in strict mode also rejects offsets pointing into the head
+    // region, where the data would alias the words of other values.
+    fn jump_to_tail(
+        bs: &[u8],
+        base_addr: usize,
+        offset: usize,
+        head_size: usize,
+        opts: &DecodeOptions,
+    ) -> Result<usize> {
+        if opts.strict && offset < head_size {
+            return Err(AbiError::OffsetIntoHead { offset, head_size }.into());
+        }
+
+        Self::jump(bs, base_addr, offset)
+    }
+
+    // Checks whether the bits above the given size are a sign extension of
+    // the size's most significant bit.
+    pub(crate) fn is_sign_extended(word: U256, size: usize) -> bool {
+        if size >= 256 {
+            return true;
+        }
+
+        let high_bits = word >> (size - 1);
+
+        high_bits.is_zero() || high_bits == U256::MAX >> (size - 1)
+    }
+
+    // Parses a decimal or `0x` prefixed hex unsigned integer.
+    fn parse_uint_literal(s: &str) -> Result<U256> {
+        let parsed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(digits) if !digits.is_empty() => U256::from_str_radix(digits, 16).ok(),
+            Some(_) => None,
+            None if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) => {
+                U256::from_dec_str(s).ok()
+            }
+            None => None,
+        };
+
+        parsed.ok_or_else(|| anyhow!("invalid integer: {}", s))
+    }
+
+    // Parses a string literal, unquoting it if it's double quoted.
+    fn parse_string_literal(s: &str) -> Result<String> {
+        let quoted = match s.strip_prefix('"') {
+            Some(quoted) => quoted,
+            None => return Ok(s.to_string()),
+        };
+
+        let mut result = String::new();
+        let mut chars = quoted.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if chars.as_str().is_empty() => return Ok(result),
+                '\\' => match chars.next() {
+                    Some(c @ ('"' | '\\')) => result.push(c),
+                    Some('n') => result.push('\n'),
+                    _ => return Err(anyhow!("invalid escape sequence in string: {}", s)),
+                },
+                '"' => return Err(anyhow!("unexpected quote in string: {}", s)),
+                c => result.push(c),
+            }
+        }
+
+        Err(anyhow!("unterminated string: {}", s))
+    }
+
+    // Parses a delimited list literal whose items have the given type.
+    fn parse_list_literal(s: &str, open: char, close: char, ty: &Type) -> Result<Vec<Value>> {
+        Self::split_list_literal(s, open, close)?
+            .into_iter()
+            .map(|item| Self::parse(item, ty))
+            .collect()
+    }
+
+    // Splits a delimited list literal on its top level commas, skipping the ones
+    // in nested lists and quoted strings.
+    fn split_list_literal(s: &str, open: char, close: char) -> Result<Vec<&str>> {
+        let inner = s
+            .strip_prefix(open)
+            .and_then(|s| s.strip_suffix(close))
+            .ok_or_else(|| anyhow!("expected {}...{} literal, got: {}", open, close, s))?;
+
+        if inner.trim().is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut items = vec![];
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut start = 0;
+
+        for (i, c) in inner.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_string => escaped = true,
+                '"' => in_string = !in_string,
+                _ if in_string => {}
+                '[' | '(' => depth += 1,
+                ']' | ')' => {
+                    depth = depth
+                        .checked_sub(1)
+                        .ok_or_else(|| anyhow!("unbalanced brackets in: {}", s))?
+                }
+                ',' if depth == 0 => {
+                    items.push(&inner[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
             }
         }
+
+        if depth != 0 || in_string {
+            return Err(anyhow!("unbalanced brackets or quotes in: {}", s));
+        }
+
+        items.push(&inner[start..]);
+
+        Ok(items)
+    }
+
+    fn check_elements(values: &[Value], ty: &Type) -> Result<()> {
+        match values.iter().position(|value| value.type_of() != *ty) {
+            Some(i) => Err(anyhow!(
+                "array element {} has type {}, expected {}",
+                i,
+                values[i].type_of(),
+                ty
+            )),
+            None => Ok(()),
+        }
+    }
+
+    // Number of bytes the value takes in an encoding, including its offset in the
+    // head if it's dynamic.
+    fn value_encoded_size(&self) -> usize {
+        match self {
+            Value::String(s) => 64 + Self::padded32_size(s.len()),
+            Value::Bytes(bytes) => 64 + Self::padded32_size(bytes.len()),
+            Value::Array(values, _) => 64 + Self::encoded_size(values),
+            Value::FixedArray(values, ty) => {
+                let offset_size = if ty.is_dynamic() { 32 } else { 0 };
+
+                offset_size + Self::encoded_size(values)
+            }
+            Value::Tuple(values) => {
+                let offset_size = if self.is_dynamic() { 32 } else { 0 };
+
+                offset_size
+                    + values
+                        .iter()
+                        .map(|(_, value)| value.value_encoded_size())
+                        .sum::<usize>()
+            }
+            _ => 32,
+        }
+    }
+
+    // Whether the value's type is dynamic, without building the type.
+    fn is_dynamic(&self) -> bool {
+        match self {
+            Value::String(_) | Value::Bytes(_) | Value::Array(_, _) => true,
+            Value::FixedArray(_, ty) => ty.is_dynamic(),
+            Value::Tuple(values) => values.iter().any(|(_, value)| value.is_dynamic()),
+            _ => false,
+        }
+    }
+
+    // Sign extends the lower `size` bits of the word to 256 bits.
+    fn sign_extend(word: U256, size: usize) -> U256 {
+        if size == 0 || size >= 256 {
+            return word;
+        }
+
+        let high_bits = U256::MAX << size;
+
+        if word.bit(size - 1) {
+            word | high_bits
+        } else {
+            word & !high_bits
+        }
+    }
+
+    // Formats a signed integer as decimal, interpreting the lower `size` bits
+    // of the word as two's complement.
+    fn int_to_string(word: U256, size: usize) -> String {
+        let word = Self::sign_extend(word, size);
+
+        if !word.bit(255) {
+            return word.to_string();
+        }
+
+        format!("-{}", (!word).overflowing_add(U256::one()).0)
     }
 
-    fn encode_bytes(buf: &mut Vec<u8>, bytes: &[u8], mut alloc_offset: usize) -> usize {
-        let padded_bytes_len = Self::padded32_size(bytes.len());
-        buf.resize(buf.len() + 32 + padded_bytes_len, 0);
+    fn encode_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+        let start = buf.len();
+        buf.resize(start + 32 + Self::padded32_size(bytes.len()), 0);
 
         // write bytes size
-        U256::from(bytes.len()).to_big_endian(&mut buf[alloc_offset..(alloc_offset + 32)]);
-        alloc_offset += 32;
+        U256::from(bytes.len()).to_big_endian(&mut buf[start..(start + 32)]);
 
         // write bytes
-        buf[alloc_offset..(alloc_offset + bytes.len())].copy_from_slice(bytes);
-
-        alloc_offset + padded_bytes_len
+        buf[(start + 32)..(start + 32 + bytes.len())].copy_from_slice(bytes);
     }
 
     // Computes the padded size for a given size, e.g.:
@@ -397,13 +1498,117 @@ impl Value {
     }
 }
 
+/// Formats values as Solidity like literals: decimal integers, `0x` prefixed hex
+/// addresses and bytes, double quoted strings, `[a, b]` arrays and `(a, b)` tuples.
+///
+/// The output can be parsed back with [`Value::parse`].
+impl core::fmt::Display for Value {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fn write_list<'a>(
+            f: &mut core::fmt::Formatter<'_>,
+            values: impl Iterator<Item = &'a Value>,
+        ) -> core::fmt::Result {
+            for (i, value) in values.enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", value)?;
+            }
+
+            Ok(())
+        }
+
+        match self {
+            Value::Uint(i, _) => write!(f, "{}", i),
+            Value::Int(i, size) => write!(f, "{}", Self::int_to_string(*i, *size)),
+            Value::Address(addr) => write!(f, "{:?}", addr),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::FixedBytes(bytes) | Value::Bytes(bytes) => {
+                write!(f, "0x{}", hex::encode(bytes))
+            }
+            Value::String(s) => {
+                write!(f, "\"")?;
+                for c in s.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            Value::FixedArray(values, _) | Value::Array(values, _) => {
+                write!(f, "[")?;
+                write_list(f, values.iter())?;
+                write!(f, "]")
+            }
+            Value::Tuple(values) => {
+                write!(f, "(")?;
+                write_list(f, values.iter().map(|(_, value)| value))?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
     use pretty_assertions::assert_eq;
+    use proptest::{collection, prelude::*};
     use rand::Rng;
 
+    use crate::arbitrary::any_type_and_value;
+
+    proptest! {
+        #[test]
+        fn encode_decode_roundtrip(tys_values in collection::vec(any_type_and_value(), 1..4)) {
+            let (tys, values): (Vec<_>, Vec<_>) = tys_values.into_iter().unzip();
+
+            let bs = Value::encode(&values);
+
+            prop_assert_eq!(
+                Value::decode_from_slice_with(&bs, &tys, &DecodeOptions::strict())
+                    .expect("decode_from_slice_with failed"),
+                values
+            );
+        }
+
+        #[test]
+        fn encoded_size_matches_encode(tys_values in collection::vec(any_type_and_value(), 0..4)) {
+            let values: Vec<_> = tys_values.into_iter().map(|(_, value)| value).collect();
+
+            prop_assert_eq!(Value::encoded_size(&values), Value::encode(&values).len());
+        }
+    }
+
+    #[test]
+    fn encoded_size() {
+        assert_eq!(Value::encoded_size(&[]), 0);
+        assert_eq!(Value::encoded_size(&[Value::Bool(true)]), 32);
+        assert_eq!(Value::encoded_size(&[Value::String("".to_string())]), 64);
+        assert_eq!(Value::encoded_size(&[Value::Bytes(vec![0; 33])]), 128);
+
+        let values = vec![
+            Value::Tuple(vec![
+                ("a".to_string(), Value::Uint(U256::one(), 256)),
+                ("b".to_string(), Value::String("abc".to_string())),
+            ]),
+            Value::FixedArray(
+                vec![Value::Address(H160::zero()), Value::Address(H160::zero())],
+                Type::Address,
+            ),
+            Value::Array(
+                vec![Value::Bytes(vec![1]), Value::Bytes(vec![])],
+                Type::Bytes,
+            ),
+        ];
+
+        assert_eq!(Value::encoded_size(&values), Value::encode(&values).len());
+    }
+
     #[test]
     fn decode_uint() {
         let uint: U256 = U256::exp10(18) + 1;
@@ -429,6 +1634,56 @@ mod test {
         assert_eq!(v, vec![Value::Int(uint, 256)]);
     }
 
+    #[test]
+    fn int_canonical_form() {
+        let minus_one = Value::int(-1, 8).unwrap();
+        assert_eq!(minus_one, Value::Int(U256::MAX, 8));
+
+        let strict =
+            Value::decode_from_slice_with(&[0xff; 32], &[Type::Int(8)], &DecodeOptions::strict())
+                .expect("decode_from_slice_with failed");
+        assert_eq!(strict, vec![minus_one.clone()]);
+
+        let mut bs = [0u8; 32];
+        bs[31] = 0xff;
+        let lenient =
+            Value::decode_from_slice(&bs, &[Type::Int(8)]).expect("decode_from_slice failed");
+        assert_eq!(lenient, vec![minus_one]);
+    }
+
+    #[test]
+    fn decode_int_sign_extends() {
+        let minus_five = Value::int(-5, 8).unwrap();
+
+        // int8(-5) as a full two's complement word
+        let mut bs = [0xffu8; 32];
+        bs[31] = 0xfb;
+
+        let v = Value::decode_from_slice(&bs, &[Type::Int(8)]).expect("decode_from_slice failed");
+        assert_eq!(v, vec![minus_five.clone()]);
+        assert_eq!(v[0].as_sign_extended(), Some(U256::MAX - 4));
+        assert_eq!(v[0].to_json(), "-5");
+
+        // only the low byte is set, which lenient decoding accepts
+        let mut bs = [0u8; 32];
+        bs[31] = 0xfb;
+
+        let v = Value::decode_from_slice(&bs, &[Type::Int(8)]).expect("decode_from_slice failed");
+        assert_eq!(v, vec![minus_five]);
+
+        let mut bs = [0xffu8; 32];
+        bs[31] = 0x05;
+
+        let v = Value::decode_from_slice(&bs, &[Type::Int(8)]).expect("decode_from_slice failed");
+        assert_eq!(v, vec![Value::Int(U256::from(5), 8)]);
+
+        assert_eq!(
+            Value::Int(U256::from(0xfb), 8).as_sign_extended(),
+            Some(U256::MAX - 4)
+        );
+        assert_eq!(Value::Uint(U256::from(5), 8).as_sign_extended(), None);
+    }
+
     #[test]
     fn decode_address() {
         let addr = H160::random();
@@ -464,6 +1719,51 @@ mod test {
         assert_eq!(v, vec![Value::FixedBytes(bs[0..16].to_vec())]);
     }
 
+    #[test]
+    fn decode_small_fixed_bytes_in_tuple_and_array() {
+        let mut bs = [0u8; 192];
+
+        // (bytes4,bytes16)
+        bs[0..4].copy_from_slice(&[1; 4]);
+        bs[32..48].copy_from_slice(&[2; 16]);
+        // bytes4[2]
+        bs[64..68].copy_from_slice(&[3; 4]);
+        bs[96..100].copy_from_slice(&[4; 4]);
+        // uint256
+        bs[191] = 5;
+
+        let v = Value::decode_from_slice(
+            &bs,
+            &[
+                Type::Tuple(vec![
+                    ("a".to_string(), Type::FixedBytes(4)),
+                    ("b".to_string(), Type::FixedBytes(16)),
+                ]),
+                Type::FixedArray(Box::new(Type::FixedBytes(4)), 2),
+                Type::Uint(256),
+                Type::Uint(256),
+            ],
+        )
+        .expect("decode_from_slice failed");
+
+        assert_eq!(
+            v,
+            vec![
+                Value::Tuple(vec![
+                    ("a".to_string(), Value::FixedBytes(vec![1; 4])),
+                    ("b".to_string(), Value::FixedBytes(vec![2; 16])),
+                ]),
+                Value::FixedArray(
+                    vec![Value::FixedBytes(vec![3; 4]), Value::FixedBytes(vec![4; 4])],
+                    Type::FixedBytes(4)
+                ),
+                Value::Uint(U256::zero(), 256),
+                Value::Uint(U256::from(5), 256),
+            ]
+        );
+        assert_eq!(Value::encode(&v), bs.to_vec());
+    }
+
     #[test]
     fn decode_fixed_array() {
         let mut bs = [0u8; 128];
@@ -526,188 +1826,1367 @@ mod test {
     }
 
     #[test]
-    fn decode_bytes() {
-        let mut rng = rand::thread_rng();
-
-        let mut bs = [0u8; 128];
-        bs[31] = 0x20; // big-endian bytes offset
-
-        let bytes_len: usize = rng.gen_range(0..64);
-        bs[63] = bytes_len as u8; // big-endian bytes length
+    fn decode_bytes() {
+        let mut rng = rand::thread_rng();
+
+        let mut bs = [0u8; 128];
+        bs[31] = 0x20; // big-endian bytes offset
+
+        let bytes_len: usize = rng.gen_range(0..64);
+        bs[63] = bytes_len as u8; // big-endian bytes length
+
+        for i in 0..bytes_len {
+            bs[64 + i] = rng.gen();
+        }
+
+        let v = Value::decode_from_slice(&bs, &[Type::Bytes]).expect("decode_from_slice failed");
+
+        assert_eq!(v, vec![Value::Bytes(bs[64..(64 + bytes_len)].to_vec())]);
+    }
+
+    #[test]
+    fn decode_array() {
+        let mut bs = [0u8; 192];
+        bs[31] = 0x20; // big-endian array offset
+        bs[63] = 2; // big-endian array length
+
+        // encode some data
+        let uint1 = U256::from(5);
+        let uint2 = U256::from(6);
+        let uint3 = U256::from(7);
+        let uint4 = U256::from(8);
+
+        uint1.to_big_endian(&mut bs[64..96]);
+        uint2.to_big_endian(&mut bs[96..128]);
+        uint3.to_big_endian(&mut bs[128..160]);
+        uint4.to_big_endian(&mut bs[160..192]);
+
+        let uint_arr2 = Type::FixedArray(Box::new(Type::Uint(256)), 2);
+
+        let v = Value::decode_from_slice(&bs, &[Type::Array(Box::new(uint_arr2.clone()))])
+            .expect("decode_from_slice failed");
+
+        assert_eq!(
+            v,
+            vec![Value::Array(
+                vec![
+                    Value::FixedArray(
+                        vec![Value::Uint(uint1, 256), Value::Uint(uint2, 256)],
+                        Type::Uint(256)
+                    ),
+                    Value::FixedArray(
+                        vec![Value::Uint(uint3, 256), Value::Uint(uint4, 256)],
+                        Type::Uint(256)
+                    )
+                ],
+                uint_arr2
+            )]
+        );
+    }
+
+    #[test]
+    fn decode_string_array() {
+        let long = "a string that takes more than one word";
+
+        let mut bs = [0u8; 352];
+        bs[31] = 0x20; // array offset
+        bs[63] = 3; // array length
+        bs[95] = 0x60; // element offsets, relative to the array data start
+        bs[127] = 0xa0;
+        bs[158] = 0x01; // 0x100
+        bs[191] = 1; // first element
+        bs[192] = b'a';
+        bs[255] = long.len() as u8; // second element
+        bs[256..(256 + long.len())].copy_from_slice(long.as_bytes());
+        // third element is empty
+
+        let expected_strings = vec![
+            Value::String("a".to_string()),
+            Value::String(long.to_string()),
+            Value::String(String::new()),
+        ];
+        let ty = Type::Array(Box::new(Type::String));
+
+        let v = Value::decode_from_slice(&bs, std::slice::from_ref(&ty))
+            .expect("decode_from_slice failed");
+        assert_eq!(
+            v,
+            vec![Value::Array(expected_strings.clone(), Type::String)]
+        );
+        assert_eq!(Value::encode(&v), bs.to_vec());
+
+        let v = Value::decode_from_slice(&bs, &[Type::Array(Box::new(Type::Bytes))])
+            .expect("decode_from_slice failed");
+        assert_eq!(
+            v,
+            vec![Value::Array(
+                vec![
+                    Value::Bytes(b"a".to_vec()),
+                    Value::Bytes(long.as_bytes().to_vec()),
+                    Value::Bytes(vec![]),
+                ],
+                Type::Bytes
+            )]
+        );
+    }
+
+    #[test]
+    fn decode_address_array_after_dynamic_param() {
+        let addr1 = H160::from_low_u64_be(0x1111);
+        let addr2 = H160::from_low_u64_be(0x2222);
+
+        let mut bs = [0u8; 224];
+        bs[31] = 0x40; // string offset
+        bs[63] = 0x80; // address array offset
+        bs[95] = 2; // string length
+        bs[96..98].copy_from_slice(b"hi");
+        bs[159] = 2; // address array length
+        bs[172..192].copy_from_slice(addr1.as_bytes());
+        bs[204..224].copy_from_slice(addr2.as_bytes());
+
+        let v =
+            Value::decode_from_slice(&bs, &[Type::String, Type::Array(Box::new(Type::Address))])
+                .expect("decode_from_slice failed");
+
+        assert_eq!(
+            v,
+            vec![
+                Value::String("hi".to_string()),
+                Value::Array(
+                    vec![Value::Address(addr1), Value::Address(addr2)],
+                    Type::Address
+                ),
+            ]
+        );
+
+        let values = vec![
+            Value::Bytes(vec![0xab; 40]),
+            Value::Array(vec![Value::Address(addr2)], Type::Address),
+            Value::Uint(U256::from(7), 256),
+            Value::Array(vec![], Type::Address),
+        ];
+        let tys: Vec<_> = values.iter().map(Value::type_of).collect();
+
+        let v = Value::decode_from_slice(&Value::encode(&values), &tys)
+            .expect("decode_from_slice failed");
+        assert_eq!(v, values);
+    }
+
+    #[test]
+    fn decode_address_arrays() {
+        let addrs = [
+            H160::from_slice(&hex::decode("0000000000000000000000000000000000000001").unwrap()),
+            H160::from_slice(&hex::decode("dac17f958d2ee523a2206206994597c13d831ec7").unwrap()),
+            H160::from_slice(&hex::decode("ffffffffffffffffffffffffffffffffffffffff").unwrap()),
+        ];
+
+        // address[3] is encoded in place, address[] is encoded after its offset and length
+        let mut bs = [0u8; 256];
+        bs[96 + 31] = 0x80; // address array offset
+        bs[128 + 31] = 3; // address array length
+        for (i, addr) in addrs.iter().enumerate() {
+            bs[(i * 32 + 12)..(i * 32 + 32)].copy_from_slice(addr.as_bytes());
+            bs[(160 + i * 32 + 12)..(160 + i * 32 + 32)].copy_from_slice(addr.as_bytes());
+        }
+
+        let v = Value::decode_from_slice(
+            &bs,
+            &[
+                Type::FixedArray(Box::new(Type::Address), 3),
+                Type::Array(Box::new(Type::Address)),
+            ],
+        )
+        .expect("decode_from_slice failed");
+
+        let addr_values: Vec<_> = addrs.iter().copied().map(Value::Address).collect();
+        assert_eq!(
+            v,
+            vec![
+                Value::FixedArray(addr_values.clone(), Type::Address),
+                Value::Array(addr_values, Type::Address),
+            ]
+        );
+        assert_eq!(Value::encode(&v), bs);
+    }
+
+    #[test]
+    fn decode_many_dynamic_params() {
+        // f(string, bytes, uint256[]) arguments, all sharing the tail region
+        let mut bs = [0u8; 320];
+        bs[31] = 0x60; // string offset
+        bs[63] = 0xa0; // bytes offset
+        bs[95] = 0xe0; // array offset
+        bs[127] = 5; // string length
+        bs[128..133].copy_from_slice(b"hello");
+        bs[191] = 3; // bytes length
+        bs[192..195].copy_from_slice(&[1, 2, 3]);
+        bs[255] = 2; // array length
+        bs[287] = 7;
+        bs[319] = 8;
+
+        let tys = [
+            Type::String,
+            Type::Bytes,
+            Type::Array(Box::new(Type::Uint(256))),
+        ];
+        let expected = vec![
+            Value::String("hello".to_string()),
+            Value::Bytes(vec![1, 2, 3]),
+            Value::Array(
+                vec![
+                    Value::Uint(U256::from(7), 256),
+                    Value::Uint(U256::from(8), 256),
+                ],
+                Type::Uint(256),
+            ),
+        ];
+
+        let v = Value::decode_from_slice(&bs, &tys).expect("decode_from_slice failed");
+        assert_eq!(v, expected);
+        assert_eq!(Value::encode(&v), bs);
+
+        // offsets stay relative to the start of the head block after a selector
+        let mut call_data = vec![0xde, 0xad, 0xbe, 0xef];
+        call_data.extend_from_slice(&bs);
+
+        let v =
+            Value::decode_from_slice_at(&call_data, &tys, 4).expect("decode_from_slice_at failed");
+        assert_eq!(v, expected);
+    }
+
+    #[test]
+    fn decode_fixed_tuple() {
+        let mut bs = [0u8; 96];
+
+        // encode some data
+        let uint1 = U256::from(5);
+        let uint2 = U256::from(6);
+        let addr = H160::random();
+
+        uint1.to_big_endian(&mut bs[0..32]);
+        uint2.to_big_endian(&mut bs[32..64]);
+        bs[76..96].copy_from_slice(addr.as_fixed_bytes());
+
+        let v = Value::decode_from_slice(
+            &bs,
+            &[Type::Tuple(vec![
+                ("a".to_string(), Type::Uint(256)),
+                ("b".to_string(), Type::Uint(256)),
+                ("c".to_string(), Type::Address),
+            ])],
+        )
+        .expect("decode_from_slice failed");
+
+        assert_eq!(
+            v,
+            vec![Value::Tuple(vec![
+                ("a".to_string(), Value::Uint(uint1, 256)),
+                ("b".to_string(), Value::Uint(uint2, 256)),
+                ("c".to_string(), Value::Address(addr))
+            ])]
+        );
+    }
+
+    #[test]
+    fn decode_tuple() {
+        let mut bs = [0u8; 192];
+        bs[31] = 0x20; // big-endian tuple offset
+
+        // encode some data
+        let uint1 = U256::from(5);
+        let s = "abc".to_string();
+        let addr = H160::random();
+
+        uint1.to_big_endian(&mut bs[32..64]);
+        bs[95] = 0x60; // big-endian string offset
+        bs[108..128].copy_from_slice(addr.as_fixed_bytes());
+        bs[159] = 3; // big-endian string len;
+        bs[160..(160 + s.len())].copy_from_slice(s.as_bytes());
+
+        let v = Value::decode_from_slice(
+            &bs,
+            &[Type::Tuple(vec![
+                ("a".to_string(), Type::Uint(256)),
+                ("b".to_string(), Type::String),
+                ("c".to_string(), Type::Address),
+            ])],
+        )
+        .expect("decode_from_slice failed");
+
+        assert_eq!(
+            v,
+            vec![Value::Tuple(vec![
+                ("a".to_string(), Value::Uint(uint1, 256)),
+                ("b".to_string(), Value::String(s)),
+                ("c".to_string(), Value::Address(addr))
+            ])]
+        );
+    }
+
+    #[test]
+    fn decode_static_tuple_array() {
+        // (uint256,address)[]: [(1, 0x1111...), (2, 0x2222...)]
+        let input = "000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000001000000000000000000000000111111111111111111111111111111111111111100000000000000000000000000000000000000000000000000000000000000020000000000000000000000002222222222222222222222222222222222222222";
+        let bs = hex::decode(input).unwrap();
+
+        let tuple_ty = Type::Tuple(vec![
+            ("a".to_string(), Type::Uint(256)),
+            ("b".to_string(), Type::Address),
+        ]);
+
+        let v = Value::decode_from_slice(&bs, &[Type::Array(Box::new(tuple_ty.clone()))])
+            .expect("decode_from_slice failed");
+
+        let expected = vec![Value::Array(
+            vec![
+                Value::Tuple(vec![
+                    ("a".to_string(), Value::Uint(U256::from(1), 256)),
+                    ("b".to_string(), Value::Address(H160::repeat_byte(0x11))),
+                ]),
+                Value::Tuple(vec![
+                    ("a".to_string(), Value::Uint(U256::from(2), 256)),
+                    ("b".to_string(), Value::Address(H160::repeat_byte(0x22))),
+                ]),
+            ],
+            tuple_ty,
+        )];
+
+        assert_eq!(v, expected);
+        assert_eq!(Value::encode(&expected), bs);
+    }
+
+    #[test]
+    fn decode_dynamic_tuple_array() {
+        // (uint256,string)[]: [(1, "a"), (2, "bc")]
+        let input = "00000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000000161000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000026263000000000000000000000000000000000000000000000000000000000000";
+        let bs = hex::decode(input).unwrap();
+
+        let tuple_ty = Type::Tuple(vec![
+            ("a".to_string(), Type::Uint(256)),
+            ("b".to_string(), Type::String),
+        ]);
+
+        let v = Value::decode_from_slice(&bs, &[Type::Array(Box::new(tuple_ty.clone()))])
+            .expect("decode_from_slice failed");
+
+        let expected = vec![Value::Array(
+            vec![
+                Value::Tuple(vec![
+                    ("a".to_string(), Value::Uint(U256::from(1), 256)),
+                    ("b".to_string(), Value::String("a".to_string())),
+                ]),
+                Value::Tuple(vec![
+                    ("a".to_string(), Value::Uint(U256::from(2), 256)),
+                    ("b".to_string(), Value::String("bc".to_string())),
+                ]),
+            ],
+            tuple_ty,
+        )];
+
+        assert_eq!(v, expected);
+        assert_eq!(Value::encode(&expected), bs);
+    }
+
+    #[test]
+    fn decode_dynamic_tuple_fixed_array_in_array() {
+        // (uint256,string)[2][]: [[(1, "a"), (2, "bc")]]
+        //
+        // each tuple's string offset is relative to the tuple itself, which is in turn
+        // located relative to the fixed array, located relative to the outer array data
+        let input = "000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000000161000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000026263000000000000000000000000000000000000000000000000000000000000";
+        let bs = hex::decode(input).unwrap();
+
+        let tuple_ty = Type::Tuple(vec![
+            ("a".to_string(), Type::Uint(256)),
+            ("b".to_string(), Type::String),
+        ]);
+        let fixed_arr_ty = Type::FixedArray(Box::new(tuple_ty.clone()), 2);
+
+        let v = Value::decode_from_slice(&bs, &[Type::Array(Box::new(fixed_arr_ty.clone()))])
+            .expect("decode_from_slice failed");
+
+        let expected = vec![Value::Array(
+            vec![Value::FixedArray(
+                vec![
+                    Value::Tuple(vec![
+                        ("a".to_string(), Value::Uint(U256::from(1), 256)),
+                        ("b".to_string(), Value::String("a".to_string())),
+                    ]),
+                    Value::Tuple(vec![
+                        ("a".to_string(), Value::Uint(U256::from(2), 256)),
+                        ("b".to_string(), Value::String("bc".to_string())),
+                    ]),
+                ],
+                tuple_ty,
+            )],
+            fixed_arr_ty,
+        )];
+
+        assert_eq!(v, expected);
+        assert_eq!(Value::encode(&expected), bs);
+    }
+
+    #[test]
+    fn decode_many() {
+        // function f(string memory x, uint32 y, uint32[][2] memory z)
+        let tys = vec![
+            Type::String,
+            Type::Uint(32),
+            Type::FixedArray(Box::new(Type::Array(Box::new(Type::Uint(32)))), 2),
+        ];
+
+        // f("abc", 5, [[1, 2], [3]])
+        let input = "0000000000000000000000000000000000000000000000000000000000000060000000000000000000000000000000000000000000000000000000000000000500000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000036162630000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000003";
+        let mut bs = [0u8; 384];
+        hex::decode_to_slice(input, &mut bs).unwrap();
+
+        let v = Value::decode_from_slice(&bs, &tys).expect("decode_from_slice failed");
+
+        assert_eq!(
+            v,
+            vec![
+                Value::String("abc".to_string()),
+                Value::Uint(U256::from(5), 32),
+                Value::FixedArray(
+                    vec![
+                        Value::Array(
+                            vec![
+                                Value::Uint(U256::from(1), 32),
+                                Value::Uint(U256::from(2), 32),
+                            ],
+                            Type::Uint(32)
+                        ),
+                        Value::Array(vec![Value::Uint(U256::from(3), 32)], Type::Uint(32)),
+                    ],
+                    Type::Array(Box::new(Type::Uint(32)))
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn decode_byte_array() {
+        let tys = vec![Type::Uint(256), Type::Array(Box::new(Type::Bytes))];
+
+        let input = "0000000000000000000000000000000000000000000000000000000062262ba1000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000016000000000000000000000000000000000000000000000000000000000000000e404e45aaf000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200000000000000000000000000000000000000000000000000000000000001f4000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000084b6a5c40000000000000000000000000000000000000000000000000bd373e0061c7e7f94000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004449404b7c00000000000000000000000000000000000000000000000bd373e0061c7e7f9400000000000000000000000016ee789b50d3d49b8f71b5314c367e3fef24d74600000000000000000000000000000000000000000000000000000000";
+        let mut bs = [0u8; 576];
+        hex::decode_to_slice(input, &mut bs).unwrap();
+
+        let values = Value::decode_from_slice(&bs, &tys).expect("decode_from_slice failed");
+
+        assert_eq!(values, vec![
+                Value::Uint(U256::from_dec_str("1646668705").unwrap(), 256),
+                Value::Array(vec![
+                    Value::Bytes(hex::decode("04e45aaf000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200000000000000000000000000000000000000000000000000000000000001f4000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000084b6a5c40000000000000000000000000000000000000000000000000bd373e0061c7e7f940000000000000000000000000000000000000000000000000000000000000000").unwrap()),
+                    Value::Bytes(hex::decode("49404b7c00000000000000000000000000000000000000000000000bd373e0061c7e7f9400000000000000000000000016ee789b50d3d49b8f71b5314c367e3fef24d746").unwrap()),
+                ], Type::Bytes),
+            ]);
+    }
+
+    #[test]
+    fn decode_strict_address() {
+        let addr = H160::random();
+
+        let mut bs = [0u8; 32];
+        bs[0] = 0xff; // dirty high bytes
+        bs[12..32].copy_from_slice(addr.as_bytes());
+
+        let v = Value::decode_from_slice(&bs, &[Type::Address]).expect("decode_from_slice failed");
+        assert_eq!(v, vec![Value::Address(addr)]);
+
+        assert!(
+            Value::decode_from_slice_with(&bs, &[Type::Address], &DecodeOptions::strict()).is_err()
+        );
+    }
+
+    #[test]
+    fn decode_strict_bool() {
+        let mut bs = [0u8; 32];
+        bs[31] = 2;
+
+        let v = Value::decode_from_slice(&bs, &[Type::Bool]).expect("decode_from_slice failed");
+        assert_eq!(v, vec![Value::Bool(false)]);
+
+        assert!(
+            Value::decode_from_slice_with(&bs, &[Type::Bool], &DecodeOptions::strict()).is_err()
+        );
+    }
+
+    #[test]
+    fn decode_strict_fixed_bytes() {
+        let mut bs = [0u8; 32];
+        bs[0..4].copy_from_slice(&[1, 2, 3, 4]);
+        bs[31] = 1; // dirty padding
+
+        let v = Value::decode_from_slice(&bs, &[Type::FixedBytes(4)])
+            .expect("decode_from_slice failed");
+        assert_eq!(v, vec![Value::FixedBytes(vec![1, 2, 3, 4])]);
+
+        assert!(Value::decode_from_slice_with(
+            &bs,
+            &[Type::FixedBytes(4)],
+            &DecodeOptions::strict()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn decode_strict_uint_and_int() {
+        let mut bs = [0u8; 32];
+        bs[30] = 1;
+        bs[31] = 0xff;
+
+        assert!(Value::decode_from_slice(&bs, &[Type::Uint(8)]).is_ok());
+        assert!(
+            Value::decode_from_slice_with(&bs, &[Type::Uint(8)], &DecodeOptions::strict()).is_err()
+        );
+        assert!(
+            Value::decode_from_slice_with(&bs, &[Type::Uint(16)], &DecodeOptions::strict()).is_ok()
+        );
+
+        // int8(-1), sign extended to 256 bits
+        let bs = [0xffu8; 32];
+        assert!(
+            Value::decode_from_slice_with(&bs, &[Type::Int(8)], &DecodeOptions::strict()).is_ok()
+        );
+
+        // int8(-1) without sign extension
+        let mut bs = [0u8; 32];
+        bs[31] = 0xff;
+        assert!(Value::decode_from_slice(&bs, &[Type::Int(8)]).is_ok());
+        assert!(
+            Value::decode_from_slice_with(&bs, &[Type::Int(8)], &DecodeOptions::strict()).is_err()
+        );
+    }
+
+    #[test]
+    fn decode_strict_offset_into_head() {
+        // uint256 0 followed by a bytes offset pointing to byte 0, so the uint word
+        // doubles as the bytes length
+        let bs = [0u8; 64];
+        let tys = [Type::Uint(256), Type::Bytes];
+
+        let v = Value::decode_from_slice(&bs, &tys).expect("decode_from_slice failed");
+        assert_eq!(
+            v,
+            vec![Value::Uint(U256::zero(), 256), Value::Bytes(vec![])]
+        );
+
+        let err = Value::decode_from_slice_with(&bs, &tys, &DecodeOptions::strict())
+            .expect_err("decode_from_slice_with succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::OffsetIntoHead {
+                offset: 0,
+                head_size: 64
+            })
+        );
+
+        // offsets nested in dynamic tuples are relative to the tuple's own head
+        let mut bs = [0u8; 96];
+        bs[31] = 0x20; // tuple offset
+        let tys = [Type::Tuple(vec![
+            ("a".to_string(), Type::Uint(256)),
+            ("b".to_string(), Type::String),
+        ])];
+
+        assert!(Value::decode_from_slice(&bs, &tys).is_ok());
+
+        let err = Value::decode_from_slice_with(&bs, &tys, &DecodeOptions::strict())
+            .expect_err("decode_from_slice_with succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::OffsetIntoHead {
+                offset: 0,
+                head_size: 64
+            })
+        );
+    }
+
+    #[test]
+    fn decode_huge_fixed_array() {
+        let ty = "uint256[576460752303423488]"
+            .parse::<Type>()
+            .expect("parse failed");
+        let tys = [ty.clone(), Type::Bytes];
+        let bs = [0u8; 64];
+
+        let err = Value::decode_from_slice(&bs, &tys).expect_err("decode succeeded");
+        assert!(err.to_string().contains("reached end of input"));
+        assert!(Value::decode_from_slice_with_ranges(&bs, &tys).is_err());
+        assert!(Value::decode_from_slice_with(&bs, &tys, &DecodeOptions::strict()).is_err());
+
+        let tuple = Type::Tuple(vec![
+            ("a".to_string(), ty.clone()),
+            ("b".to_string(), ty),
+            ("c".to_string(), Type::Bytes),
+        ]);
+        assert!(Value::decode_from_slice_with(&bs, &[tuple], &DecodeOptions::strict()).is_err());
+    }
+
+    #[test]
+    fn decode_invalid_utf8_string() {
+        let mut bs = [0u8; 96];
+        bs[31] = 0x20; // big-endian string offset
+        bs[63] = 3; // big-endian string length
+        bs[64..67].copy_from_slice(&[b'a', 0xff, b'b']);
+
+        let v = Value::decode_from_slice(&bs, &[Type::String]).expect("decode_from_slice failed");
+        assert_eq!(v, vec![Value::String("a\u{fffd}b".to_string())]);
+
+        let err = Value::decode_from_slice_with(&bs, &[Type::String], &DecodeOptions::strict())
+            .expect_err("decode_from_slice_with succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::InvalidUtf8 { offset: 65 })
+        );
+    }
+
+    #[test]
+    fn decode_max_depth() {
+        let elem_ty = (0..39).fold(Type::Uint(256), |ty, _| Type::Array(Box::new(ty)));
+        let ty = Type::Array(Box::new(elem_ty.clone()));
+
+        // an empty outer array
+        let mut bs = [0u8; 64];
+        bs[31] = 0x20;
+
+        let err =
+            Value::decode_from_slice(&bs, std::slice::from_ref(&ty)).expect_err("decode succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::MaxDepthExceeded { max_depth: 32 })
+        );
+
+        let opts = DecodeOptions {
+            max_depth: 41,
+            ..DecodeOptions::default()
+        };
+        let values = Value::decode_from_slice_with(&bs, std::slice::from_ref(&ty), &opts)
+            .expect("decode_from_slice_with failed");
+        assert_eq!(values, vec![Value::Array(vec![], elem_ty)]);
+    }
+
+    #[test]
+    fn decode_layout() {
+        let tys = [
+            Type::Address,
+            Type::Tuple(vec![
+                ("a".to_string(), Type::Uint(256)),
+                ("b".to_string(), Type::FixedArray(Box::new(Type::Bool), 2)),
+            ]),
+            Type::String,
+            Type::Uint(8),
+        ];
+        let layout = DecodeLayout::precompute(&tys);
+
+        assert_eq!(layout.head_size(), 32 * 6);
+        assert!(layout.is_dynamic());
+        assert!(!DecodeLayout::precompute(&tys[..2]).is_dynamic());
+
+        for i in 0..3u64 {
+            let values = vec![
+                Value::Address(H160::from_low_u64_be(i)),
+                Value::Tuple(vec![
+                    ("a".to_string(), Value::Uint(U256::from(i), 256)),
+                    (
+                        "b".to_string(),
+                        Value::FixedArray(vec![Value::Bool(true); 2], Type::Bool),
+                    ),
+                ]),
+                Value::String("x".repeat(i as usize * 20)),
+                Value::Uint(U256::from(i), 8),
+            ];
+            let bs = Value::encode(&values);
+
+            assert_eq!(layout.decode(&bs).expect("decode failed"), values);
+            assert_eq!(
+                layout.decode(&bs).unwrap(),
+                Value::decode_from_slice(&bs, &tys).unwrap()
+            );
+        }
+
+        assert!(layout.decode(&[0u8; 32 * 5]).is_err());
+
+        // nested structure comes from the layout, e.g. the head size of a dynamic
+        // tuple whose string offset points back into it
+        let mut bs = [0u8; 96];
+        bs[31] = 0x20;
+        let tys = [Type::Tuple(vec![
+            ("a".to_string(), Type::Uint(256)),
+            ("b".to_string(), Type::String),
+        ])];
+
+        assert!(DecodeLayout::precompute(&tys).decode(&bs).is_ok());
+
+        let err = DecodeLayout::precompute_with(&tys, &DecodeOptions::strict())
+            .decode(&bs)
+            .expect_err("decode succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::OffsetIntoHead {
+                offset: 0,
+                head_size: 64
+            })
+        );
+
+        let deep_ty = (0..40).fold(Type::Uint(256), |ty, _| Type::Array(Box::new(ty)));
+        let err = DecodeLayout::precompute(&[deep_ty])
+            .decode(&[0u8; 64])
+            .expect_err("decode succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::MaxDepthExceeded { max_depth: 32 })
+        );
+    }
+
+    #[test]
+    fn decode_max_elements() {
+        let opts = DecodeOptions {
+            max_elements: Some(1000),
+            ..DecodeOptions::default()
+        };
+
+        // an array of empty tuples declaring a billion elements, which take no input
+        let mut bs = [0u8; 64];
+        bs[31] = 0x20;
+        U256::from(1_000_000_000).to_big_endian(&mut bs[32..64]);
+
+        let err = Value::decode_from_slice_with(
+            &bs,
+            &[Type::Array(Box::new(Type::Tuple(vec![])))],
+            &opts,
+        )
+        .expect_err("decode succeeded");
+        assert_eq!(
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::MaxElementsExceeded { max_elements: 1000 })
+        );
+
+        // the top level value and its 3 elements
+        let bs = Value::encode(&[Value::Array(vec![Value::Bool(true); 3], Type::Bool)]);
+        let tys = [Type::Array(Box::new(Type::Bool))];
+
+        let opts = DecodeOptions {
+            max_elements: Some(4),
+            ..DecodeOptions::default()
+        };
+        assert!(Value::decode_from_slice_with(&bs, &tys, &opts).is_ok());
+
+        let opts = DecodeOptions {
+            max_elements: Some(3),
+            ..DecodeOptions::default()
+        };
+        assert!(Value::decode_from_slice_with(&bs, &tys, &opts).is_err());
+    }
+
+    #[test]
+    fn decode_huge_length_and_offset() {
+        for word in [U256::from(u64::MAX), U256::from(u64::MAX - 31), U256::MAX] {
+            // a string whose length word does not fit the input
+            let mut bs = [0u8; 96];
+            bs[31] = 0x20;
+            word.to_big_endian(&mut bs[32..64]);
+
+            assert!(Value::decode_from_slice(&bs, &[Type::String]).is_err());
+            assert!(Value::decode_from_slice(&bs, &[Type::Bytes]).is_err());
+
+            // a bytes offset that points far past the input
+            let mut bs = [0u8; 64];
+            word.to_big_endian(&mut bs[0..32]);
+
+            assert!(Value::decode_from_slice(&bs, &[Type::Bytes]).is_err());
+            assert!(
+                Value::decode_from_slice(&bs, &[Type::Array(Box::new(Type::Uint(256)))]).is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn decode_from_slice_at() {
+        let values = vec![
+            Value::Uint(U256::from(7), 256),
+            Value::String("abc".to_string()),
+            Value::Array(vec![Value::Bool(true)], Type::Bool),
+        ];
+        let tys: Vec<_> = values.iter().map(Value::type_of).collect();
+
+        let mut bs = vec![0xee; 5];
+        bs.extend(Value::encode(&values));
+        bs.extend([0xee; 3]);
+
+        assert_eq!(
+            Value::decode_from_slice_at(&bs, &tys, 5).expect("decode_from_slice_at failed"),
+            values
+        );
+        assert_eq!(
+            Value::decode_from_slice_at(&bs[5..], &tys, 0).expect("decode_from_slice_at failed"),
+            values
+        );
+
+        assert!(Value::decode_from_slice_at(&bs, &tys, bs.len()).is_err());
+        assert!(Value::decode_from_slice_at(&bs, &tys, usize::MAX).is_err());
+    }
+
+    #[test]
+    fn decode_from_slice_with_ranges() {
+        let values = vec![
+            Value::Uint(U256::from(7), 256),
+            Value::String("abc".to_string()),
+            Value::Array(
+                vec![Value::Uint(U256::one(), 8), Value::Uint(U256::from(2), 8)],
+                Type::Uint(8),
+            ),
+            Value::FixedArray(vec![Value::Bool(true), Value::Bool(false)], Type::Bool),
+        ];
+        let tys: Vec<_> = values.iter().map(Value::type_of).collect();
+        let bs = Value::encode(&values);
+
+        let decoded =
+            Value::decode_from_slice_with_ranges(&bs, &tys).expect("decode_from_slice failed");
+
+        assert_eq!(
+            decoded,
+            vec![
+                (values[0].clone(), 0..32),
+                // length word and one word of data
+                (values[1].clone(), 160..224),
+                // length word and two elements
+                (values[2].clone(), 224..320),
+                (values[3].clone(), 96..160),
+            ]
+        );
+        assert_eq!(bs.len(), 320);
+
+        // a string whose data padding is missing at the end of the input
+        let bs = &bs[..(160 + 32 + 3)];
+        let decoded =
+            Value::decode_from_slice_with_ranges(bs, &tys[..2]).expect("decode_from_slice failed");
+        assert_eq!(decoded[1], (values[1].clone(), 160..195));
+    }
+
+    #[test]
+    fn decode_from_slice_with_ranges_lossy_string() {
+        let mut bs = [0u8; 128];
+        bs[31] = 0x20; // string offset
+        bs[63] = 31; // string length
+        bs[64..95].copy_from_slice(&[0xff; 31]);
+        bs[96..128].copy_from_slice(&[0xaa; 32]); // trailing data
+
+        // each invalid byte is replaced by the 3 bytes long U+FFFD, so the value's
+        // canonical encoding is longer than what was read
+        let decoded = Value::decode_from_slice_with_ranges(&bs, &[Type::String])
+            .expect("decode_from_slice failed");
+        assert_eq!(
+            decoded,
+            vec![(Value::String("\u{fffd}".repeat(31)), 32..96)]
+        );
+    }
+
+    #[test]
+    fn decode_from_slice_with_ranges_non_canonical_layout() {
+        let tys = [Type::Array(Box::new(Type::String))];
+
+        // string[] whose element tails are out of order and apart from each other
+        let mut bs = [0xeeu8; 288];
+        let mut word = |at: usize, value: u8| {
+            bs[at..(at + 32)].copy_from_slice(&[0; 32]);
+            bs[at + 31] = value;
+        };
+        word(0, 0x20); // array offset
+        word(32, 2); // array length
+        word(64, 0xa0); // first element offset, relative to the elements' head
+        word(96, 0x40); // second element offset
+        word(128, 1); // second element length
+        word(224, 1); // first element length
+        bs[160] = b'b';
+        bs[256] = b'a';
+
+        let decoded =
+            Value::decode_from_slice_with_ranges(&bs, &tys).expect("decode_from_slice failed");
+        assert_eq!(
+            decoded,
+            vec![(
+                Value::Array(
+                    vec![
+                        Value::String("a".to_string()),
+                        Value::String("b".to_string())
+                    ],
+                    Type::String
+                ),
+                32..288
+            )]
+        );
+
+        // both elements aliasing the same tail
+        bs[95] = 0x40;
+        let decoded = Value::decode_from_slice_with_ranges(&bs[..192], &tys)
+            .expect("decode_from_slice failed");
+        assert_eq!(
+            decoded,
+            vec![(
+                Value::Array(
+                    vec![
+                        Value::String("b".to_string()),
+                        Value::String("b".to_string())
+                    ],
+                    Type::String
+                ),
+                32..192
+            )]
+        );
+    }
+
+    #[test]
+    fn parse_literals() {
+        let parse = |s: &str, ty: &str| Value::parse(s, &ty.parse().unwrap());
+
+        assert_eq!(
+            parse("255", "uint8").unwrap(),
+            Value::Uint(U256::from(255), 8)
+        );
+        assert_eq!(
+            parse("0xff", "uint8").unwrap(),
+            Value::Uint(U256::from(255), 8)
+        );
+        assert!(parse("256", "uint8").is_err());
+        assert!(parse("-1", "uint8").is_err());
+        assert!(parse("0x", "uint8").is_err());
+
+        assert_eq!(parse("-128", "int8").unwrap(), Value::int(-128, 8).unwrap());
+        assert_eq!(parse("127", "int8").unwrap(), Value::int(127, 8).unwrap());
+        assert_eq!(parse("-0x1", "int256").unwrap(), Value::Int(U256::MAX, 256));
+        assert!(parse("128", "int8").is_err());
+        assert!(parse("-129", "int8").is_err());
+
+        let addr = H160::from_low_u64_be(0xaa);
+        assert_eq!(
+            parse("0x00000000000000000000000000000000000000aa", "address").unwrap(),
+            Value::Address(addr)
+        );
+        assert!(parse("0xaa", "address").is_err());
+
+        assert_eq!(parse("true", "bool").unwrap(), Value::Bool(true));
+        assert_eq!(parse(" false ", "bool").unwrap(), Value::Bool(false));
+        assert!(parse("1", "bool").is_err());
+
+        assert_eq!(
+            parse("hello", "string").unwrap(),
+            Value::String("hello".to_string())
+        );
+        assert_eq!(
+            parse(r#""a, \"b\" \\ c""#, "string").unwrap(),
+            Value::String(r#"a, "b" \ c"#.to_string())
+        );
+        assert!(parse(r#""abc"#, "string").is_err());
+
+        assert_eq!(parse("0x0102", "bytes").unwrap(), Value::Bytes(vec![1, 2]));
+        assert_eq!(
+            parse("0x0102", "bytes2").unwrap(),
+            Value::FixedBytes(vec![1, 2])
+        );
+        assert!(parse("0x01", "bytes2").is_err());
+    }
+
+    #[test]
+    fn parse_array_and_tuple_literals() {
+        let parse = |s: &str, ty: &str| Value::parse(s, &ty.parse().unwrap());
+        let uint = |i: u64| Value::Uint(U256::from(i), 8);
+
+        assert_eq!(
+            parse("[[1, 2], [], [0x03]]", "uint8[][]").unwrap(),
+            Value::Array(
+                vec![
+                    Value::Array(vec![uint(1), uint(2)], Type::Uint(8)),
+                    Value::Array(vec![], Type::Uint(8)),
+                    Value::Array(vec![uint(3)], Type::Uint(8)),
+                ],
+                Type::Array(Box::new(Type::Uint(8)))
+            )
+        );
+        assert_eq!(
+            parse("[1,2]", "uint8[2]").unwrap(),
+            Value::FixedArray(vec![uint(1), uint(2)], Type::Uint(8))
+        );
+        assert!(parse("[1]", "uint8[2]").is_err());
+        assert!(parse("[1, [2]", "uint8[]").is_err());
+        assert!(parse("1, 2", "uint8[]").is_err());
+
+        assert_eq!(
+            parse(r#"["a,b", "c]"]"#, "string[]").unwrap(),
+            Value::Array(
+                vec![
+                    Value::String("a,b".to_string()),
+                    Value::String("c]".to_string())
+                ],
+                Type::String
+            )
+        );
+
+        let ty = Type::Tuple(vec![
+            ("a".to_string(), Type::Uint(8)),
+            ("b".to_string(), Type::Array(Box::new(Type::Bool))),
+        ]);
+        assert_eq!(
+            Value::parse("(1, [true, false])", &ty).unwrap(),
+            Value::Tuple(vec![
+                ("a".to_string(), uint(1)),
+                (
+                    "b".to_string(),
+                    Value::Array(vec![Value::Bool(true), Value::Bool(false)], Type::Bool)
+                ),
+            ])
+        );
+        assert!(Value::parse("(1)", &ty).is_err());
+    }
+
+    #[test]
+    fn display() {
+        let values = vec![
+            (Value::Uint(U256::from(5), 256), "5"),
+            (Value::int(-5, 8).unwrap(), "-5"),
+            (
+                Value::Address(H160::from_low_u64_be(0xaa)),
+                "0x00000000000000000000000000000000000000aa",
+            ),
+            (Value::Bool(true), "true"),
+            (Value::FixedBytes(vec![0xab, 0x01]), "0xab01"),
+            (Value::Bytes(vec![]), "0x"),
+            (
+                Value::String("say \"hi\"\n\\".to_string()),
+                r#""say \"hi\"\n\\""#,
+            ),
+            (
+                Value::Array(
+                    vec![Value::Uint(U256::one(), 8), Value::Uint(U256::from(2), 8)],
+                    Type::Uint(8),
+                ),
+                "[1, 2]",
+            ),
+            (Value::FixedArray(vec![], Type::Bool), "[]"),
+            (
+                Value::Tuple(vec![
+                    ("a".to_string(), Value::Bool(false)),
+                    (
+                        "b".to_string(),
+                        Value::Array(vec![Value::String("x".to_string())], Type::String),
+                    ),
+                ]),
+                r#"(false, ["x"])"#,
+            ),
+        ];
 
-        for i in 0..bytes_len {
-            bs[64 + i] = rng.gen();
+        for (value, expected) in values {
+            assert_eq!(value.to_string(), expected);
+            assert_eq!(
+                Value::parse(expected, &value.type_of()).expect("parse failed"),
+                value
+            );
         }
-
-        let v = Value::decode_from_slice(&bs, &[Type::Bytes]).expect("decode_from_slice failed");
-
-        assert_eq!(v, vec![Value::Bytes(bs[64..(64 + bytes_len)].to_vec())]);
     }
 
     #[test]
-    fn decode_array() {
-        let mut bs = [0u8; 192];
-        bs[31] = 0x20; // big-endian array offset
-        bs[63] = 2; // big-endian array length
-
-        // encode some data
-        let uint1 = U256::from(5);
-        let uint2 = U256::from(6);
-        let uint3 = U256::from(7);
-        let uint4 = U256::from(8);
+    fn decode_static_sequences() {
+        let tuple_ty = Type::Tuple(vec![
+            ("a".to_string(), Type::Uint(256)),
+            ("b".to_string(), Type::FixedArray(Box::new(Type::Bool), 2)),
+            ("c".to_string(), Type::Tuple(vec![])),
+        ]);
+        let value = Value::Tuple(vec![
+            ("a".to_string(), Value::Uint(U256::from(1), 256)),
+            (
+                "b".to_string(),
+                Value::FixedArray(vec![Value::Bool(true), Value::Bool(false)], Type::Bool),
+            ),
+            ("c".to_string(), Value::Tuple(vec![])),
+        ]);
 
-        uint1.to_big_endian(&mut bs[64..96]);
-        uint2.to_big_endian(&mut bs[96..128]);
-        uint3.to_big_endian(&mut bs[128..160]);
-        uint4.to_big_endian(&mut bs[160..192]);
+        let bs = Value::encode(&[value.clone(), Value::Uint(U256::from(2), 8)]);
+        assert_eq!(bs.len(), 128);
+        assert_eq!(
+            Value::decode_from_slice(&bs, &[tuple_ty.clone(), Type::Uint(8)])
+                .expect("decode_from_slice failed"),
+            vec![value, Value::Uint(U256::from(2), 8)]
+        );
+        assert!(Value::decode_from_slice(&bs[..95], &[tuple_ty]).is_err());
 
-        let uint_arr2 = Type::FixedArray(Box::new(Type::Uint(256)), 2);
+        // a huge static array doesn't preallocate past the input
+        let ty = Type::FixedArray(Box::new(Type::Uint(256)), usize::MAX / 64);
+        assert!(Value::decode_from_slice(&bs, &[ty]).is_err());
+    }
 
-        let v = Value::decode_from_slice(&bs, &[Type::Array(Box::new(uint_arr2.clone()))])
-            .expect("decode_from_slice failed");
+    #[test]
+    fn decode_offset_too_large() {
+        let bs = [0xffu8; 32];
 
+        let err = Value::decode_from_slice(&bs, &[Type::Bytes]).expect_err("decode succeeded");
         assert_eq!(
-            v,
-            vec![Value::Array(
-                vec![
-                    Value::FixedArray(
-                        vec![Value::Uint(uint1, 256), Value::Uint(uint2, 256)],
-                        Type::Uint(256)
-                    ),
-                    Value::FixedArray(
-                        vec![Value::Uint(uint3, 256), Value::Uint(uint4, 256)],
-                        Type::Uint(256)
-                    )
-                ],
-                uint_arr2
-            )]
+            err.downcast_ref::<AbiError>(),
+            Some(&AbiError::OffsetTooLarge { value: U256::MAX })
         );
     }
 
     #[test]
-    fn decode_fixed_tuple() {
-        let mut bs = [0u8; 96];
+    fn checked_constructors() {
+        assert_eq!(
+            Value::uint(255u64, 8).unwrap(),
+            Value::Uint(U256::from(255), 8)
+        );
+        assert_eq!(
+            Value::uint(U256::MAX, 256).unwrap(),
+            Value::Uint(U256::MAX, 256)
+        );
+        assert!(Value::uint(256u64, 8).is_err());
+        assert!(Value::uint(1u64, 7).is_err());
+        assert!(Value::uint(1u64, 264).is_err());
+
+        assert_eq!(Value::int(-5, 256).unwrap(), Value::Int(U256::MAX - 4, 256));
+        assert_eq!(Value::int(-128, 8).unwrap(), Value::Int(U256::MAX - 127, 8));
+        assert_eq!(Value::int(127, 8).unwrap(), Value::Int(U256::from(127), 8));
+        assert_eq!(
+            Value::int(i128::MIN, 128).unwrap().to_json(),
+            i128::MIN.to_string()
+        );
+        assert!(Value::int(128, 8).is_err());
+        assert!(Value::int(-129, 8).is_err());
+        assert!(Value::int(0, 0).is_err());
+
+        // int256 values outside the i128 range
+        assert_eq!(
+            Value::int_from_word(U256::MAX, 256).unwrap(),
+            Value::int(-1, 256).unwrap()
+        );
+        let int256_min = U256::one() << 255;
+        assert_eq!(
+            Value::int_from_word(int256_min, 256).unwrap(),
+            Value::Int(int256_min, 256)
+        );
+        assert_eq!(
+            Value::int_from_word(U256::MAX - 127, 8).unwrap(),
+            Value::int(-128, 8).unwrap()
+        );
+        assert_eq!(
+            Value::int_from_word(U256::from(127), 8).unwrap(),
+            Value::int(127, 8).unwrap()
+        );
+        assert!(Value::int_from_word(U256::from(128), 8).is_err());
+        assert!(Value::int_from_word(U256::MAX - 128, 8).is_err());
+        assert!(Value::int_from_word(U256::one() << 128, 128).is_err());
+        assert!(Value::int_from_word(U256::zero(), 7).is_err());
 
-        // encode some data
-        let uint1 = U256::from(5);
-        let uint2 = U256::from(6);
         let addr = H160::random();
+        assert_eq!(Value::address(addr), Value::Address(addr));
 
-        uint1.to_big_endian(&mut bs[0..32]);
-        uint2.to_big_endian(&mut bs[32..64]);
-        bs[76..96].copy_from_slice(addr.as_fixed_bytes());
+        assert_eq!(
+            Value::fixed_bytes(vec![1, 2], 2).unwrap(),
+            Value::FixedBytes(vec![1, 2])
+        );
+        assert!(Value::fixed_bytes(vec![1, 2], 3).is_err());
+        assert!(Value::fixed_bytes(vec![0; 33], 33).is_err());
 
-        let v = Value::decode_from_slice(
-            &bs,
-            &[Type::Tuple(vec![
-                ("a".to_string(), Type::Uint(256)),
-                ("b".to_string(), Type::Uint(256)),
-                ("c".to_string(), Type::Address),
-            ])],
-        )
-        .expect("decode_from_slice failed");
+        let values = vec![Value::Bool(true), Value::Bool(false)];
+        assert_eq!(
+            Value::array(values.clone(), Type::Bool).unwrap(),
+            Value::Array(values.clone(), Type::Bool)
+        );
+        assert_eq!(
+            Value::fixed_array(values.clone(), Type::Bool).unwrap(),
+            Value::FixedArray(values, Type::Bool)
+        );
 
+        let err = Value::array(
+            vec![Value::Bool(true), Value::Uint(U256::one(), 8)],
+            Type::Bool,
+        )
+        .expect_err("array created");
         assert_eq!(
-            v,
-            vec![Value::Tuple(vec![
-                ("a".to_string(), Value::Uint(uint1, 256)),
-                ("b".to_string(), Value::Uint(uint2, 256)),
-                ("c".to_string(), Value::Address(addr))
-            ])]
+            err.to_string(),
+            "array element 1 has type uint8, expected bool"
         );
+        assert!(Value::fixed_array(vec![Value::Bool(true)], Type::Uint(8)).is_err());
     }
 
     #[test]
-    fn decode_tuple() {
-        let mut bs = [0u8; 192];
-        bs[31] = 0x20; // big-endian tuple offset
+    fn hash_map_key() {
+        let mut map = std::collections::HashMap::new();
 
-        // encode some data
-        let uint1 = U256::from(5);
-        let s = "abc".to_string();
-        let addr = H160::random();
+        let value = Value::Tuple(vec![
+            ("a".to_string(), Value::Uint(U256::from(1), 256)),
+            ("b".to_string(), Value::String("abc".to_string())),
+        ]);
 
-        uint1.to_big_endian(&mut bs[32..64]);
-        bs[95] = 0x60; // big-endian string offset
-        bs[108..128].copy_from_slice(addr.as_fixed_bytes());
-        bs[159] = 3; // big-endian string len;
-        bs[160..(160 + s.len())].copy_from_slice(s.as_bytes());
+        map.insert(value.clone(), Value::encode(std::slice::from_ref(&value)));
 
-        let v = Value::decode_from_slice(
-            &bs,
-            &[Type::Tuple(vec![
-                ("a".to_string(), Type::Uint(256)),
-                ("b".to_string(), Type::String),
-                ("c".to_string(), Type::Address),
-            ])],
-        )
-        .expect("decode_from_slice failed");
+        assert_eq!(map.get(&value), Some(&Value::encode(&[value])));
+        assert_eq!(map.get(&Value::Uint(U256::from(1), 8)), None);
+    }
+
+    #[test]
+    fn type_of() {
+        assert_eq!(Value::Uint(U256::one(), 24).type_of(), Type::Uint(24));
+        assert_eq!(Value::Int(U256::one(), 8).type_of(), Type::Int(8));
+        assert_eq!(Value::Address(H160::zero()).type_of(), Type::Address);
+        assert_eq!(Value::Bool(true).type_of(), Type::Bool);
+        assert_eq!(Value::String("".to_string()).type_of(), Type::String);
+        assert_eq!(Value::FixedBytes(vec![0; 4]).type_of(), Type::FixedBytes(4));
+        assert_eq!(Value::Bytes(vec![0; 4]).type_of(), Type::Bytes);
 
         assert_eq!(
-            v,
-            vec![Value::Tuple(vec![
-                ("a".to_string(), Value::Uint(uint1, 256)),
-                ("b".to_string(), Value::String(s)),
-                ("c".to_string(), Value::Address(addr))
-            ])]
+            Value::Array(vec![], Type::Address).type_of(),
+            Type::Array(Box::new(Type::Address))
+        );
+        assert_eq!(
+            Value::FixedArray(vec![Value::Bool(true), Value::Bool(false)], Type::Bool).type_of(),
+            Type::FixedArray(Box::new(Type::Bool), 2)
+        );
+        assert_eq!(
+            Value::Tuple(vec![
+                ("a".to_string(), Value::Bool(true)),
+                ("b".to_string(), Value::Array(vec![], Type::String)),
+            ])
+            .type_of(),
+            Type::Tuple(vec![
+                ("a".to_string(), Type::Bool),
+                ("b".to_string(), Type::Array(Box::new(Type::String))),
+            ])
         );
     }
 
     #[test]
-    fn decode_many() {
-        // function f(string memory x, uint32 y, uint32[][2] memory z)
-        let tys = vec![
-            Type::String,
-            Type::Uint(32),
-            Type::FixedArray(Box::new(Type::Array(Box::new(Type::Uint(32)))), 2),
-        ];
-
-        // f("abc", 5, [[1, 2], [3]])
-        let input = "0000000000000000000000000000000000000000000000000000000000000060000000000000000000000000000000000000000000000000000000000000000500000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000036162630000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000003";
-        let mut bs = [0u8; 384];
-        hex::decode_to_slice(input, &mut bs).unwrap();
+    fn default_for() {
+        assert_eq!(
+            Value::default_for(&Type::Uint(8)),
+            Value::Uint(U256::zero(), 8)
+        );
+        assert_eq!(
+            Value::default_for(&Type::Int(256)),
+            Value::Int(U256::zero(), 256)
+        );
+        assert_eq!(
+            Value::default_for(&Type::Address),
+            Value::Address(H160::zero())
+        );
+        assert_eq!(Value::default_for(&Type::Bool), Value::Bool(false));
+        assert_eq!(
+            Value::default_for(&Type::FixedBytes(4)),
+            Value::FixedBytes(vec![0; 4])
+        );
+        assert_eq!(
+            Value::default_for(&Type::String),
+            Value::String(String::new())
+        );
+        assert_eq!(Value::default_for(&Type::Bytes), Value::Bytes(vec![]));
 
-        let v = Value::decode_from_slice(&bs, &tys).expect("decode_from_slice failed");
+        let ty = Type::Tuple(vec![
+            ("a".to_string(), Type::FixedArray(Box::new(Type::Bool), 2)),
+            ("b".to_string(), Type::Array(Box::new(Type::Address))),
+        ]);
+        let value = Value::default_for(&ty);
 
         assert_eq!(
-            v,
-            vec![
-                Value::String("abc".to_string()),
-                Value::Uint(U256::from(5), 32),
-                Value::FixedArray(
-                    vec![
-                        Value::Array(
-                            vec![
-                                Value::Uint(U256::from(1), 32),
-                                Value::Uint(U256::from(2), 32),
-                            ],
-                            Type::Uint(32)
-                        ),
-                        Value::Array(vec![Value::Uint(U256::from(3), 32)], Type::Uint(32)),
-                    ],
-                    Type::Array(Box::new(Type::Uint(32)))
+            value,
+            Value::Tuple(vec![
+                (
+                    "a".to_string(),
+                    Value::FixedArray(vec![Value::Bool(false); 2], Type::Bool)
                 ),
-            ],
+                ("b".to_string(), Value::Array(vec![], Type::Address)),
+            ])
         );
+        assert_eq!(value.type_of(), ty);
     }
 
     #[test]
-    fn decode_byte_array() {
-        let tys = vec![Type::Uint(256), Type::Array(Box::new(Type::Bytes))];
+    fn value_eq() {
+        let a = Value::Uint(U256::from(5), 256);
+        let b = Value::Uint(U256::from(5), 8);
+        assert_ne!(a, b);
+        assert!(a.value_eq(&b));
+        assert!(!a.value_eq(&Value::Uint(U256::from(6), 256)));
+        assert!(!a.value_eq(&Value::Int(U256::from(5), 256)));
+
+        // -1 as int8, with and without sign extension
+        let a = Value::Int(U256::MAX, 256);
+        let b = Value::Int(U256::from(0xff), 8);
+        assert_ne!(a, b);
+        assert!(a.value_eq(&b));
+        assert!(!a.value_eq(&Value::Int(U256::from(0xff), 256)));
+
+        let a = Value::Tuple(vec![(
+            "xs".to_string(),
+            Value::Array(vec![Value::Uint(U256::one(), 256)], Type::Uint(256)),
+        )]);
+        let b = Value::Tuple(vec![(
+            "xs".to_string(),
+            Value::Array(vec![Value::Uint(U256::one(), 32)], Type::Uint(32)),
+        )]);
+        assert_ne!(a, b);
+        assert!(a.value_eq(&b));
+
+        let c = Value::Tuple(vec![(
+            "ys".to_string(),
+            Value::Array(vec![Value::Uint(U256::one(), 32)], Type::Uint(32)),
+        )]);
+        assert!(!a.value_eq(&c));
+        assert!(!Value::Array(vec![], Type::Bool)
+            .value_eq(&Value::Array(vec![Value::Bool(true)], Type::Bool)));
+        assert!(Value::String("a".to_string()).value_eq(&Value::String("a".to_string())));
+    }
 
-        let input = "0000000000000000000000000000000000000000000000000000000062262ba1000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000016000000000000000000000000000000000000000000000000000000000000000e404e45aaf000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200000000000000000000000000000000000000000000000000000000000001f4000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000084b6a5c40000000000000000000000000000000000000000000000000bd373e0061c7e7f94000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004449404b7c00000000000000000000000000000000000000000000000bd373e0061c7e7f9400000000000000000000000016ee789b50d3d49b8f71b5314c367e3fef24d74600000000000000000000000000000000000000000000000000000000";
-        let mut bs = [0u8; 576];
-        hex::decode_to_slice(input, &mut bs).unwrap();
+    #[test]
+    fn int_to_json() {
+        let minus_five = U256::MAX - U256::from(4);
+
+        assert_eq!(Value::Int(minus_five, 256).to_json(), "-5");
+        assert_eq!(Value::Int(U256::from(5), 256).to_json(), "5");
+        assert_eq!(Value::Int(U256::MAX, 8).to_json(), "-1");
+        assert_eq!(Value::Int(U256::from(0xff), 8).to_json(), "-1");
+        assert_eq!(Value::Int(U256::from(0x80), 8).to_json(), "-128");
+        assert_eq!(Value::Int(U256::from(0x7f), 8).to_json(), "127");
+        assert_eq!(
+            Value::Int(U256::one() << 255, 256).to_json(),
+            "-57896044618658097711785492504343953926634992332820282019728792003956564819968"
+        );
+        assert_eq!(Value::Uint(U256::MAX, 256).to_json(), U256::MAX.to_string());
+    }
 
-        let values = Value::decode_from_slice(&bs, &tys).expect("decode_from_slice failed");
+    #[test]
+    fn to_json() {
+        let addr = H160::from_low_u64_be(0xabcd);
 
-        assert_eq!(values, vec![
-                Value::Uint(U256::from_dec_str("1646668705").unwrap(), 256),
-                Value::Array(vec![
-                    Value::Bytes(hex::decode("04e45aaf000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200000000000000000000000000000000000000000000000000000000000001f4000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000084b6a5c40000000000000000000000000000000000000000000000000bd373e0061c7e7f940000000000000000000000000000000000000000000000000000000000000000").unwrap()),
-                    Value::Bytes(hex::decode("49404b7c00000000000000000000000000000000000000000000000bd373e0061c7e7f9400000000000000000000000016ee789b50d3d49b8f71b5314c367e3fef24d746").unwrap()),
-                ], Type::Bytes),
-            ]);
+        let value = Value::Tuple(vec![
+            ("a".to_string(), Value::Address(addr)),
+            ("b".to_string(), Value::Bool(true)),
+            ("c".to_string(), Value::Bytes(vec![0x12, 0x34])),
+            (
+                "d".to_string(),
+                Value::Array(
+                    vec![
+                        Value::String("x".to_string()),
+                        Value::String("y".to_string()),
+                    ],
+                    Type::String,
+                ),
+            ),
+            (
+                "e".to_string(),
+                Value::Tuple(vec![
+                    ("".to_string(), Value::Uint(U256::from(1), 8)),
+                    ("".to_string(), Value::FixedBytes(vec![0xff])),
+                ]),
+            ),
+        ]);
+
+        assert_eq!(
+            value.to_json(),
+            serde_json::json!({
+                "a": "0x000000000000000000000000000000000000abcd",
+                "b": true,
+                "c": "0x1234",
+                "d": ["x", "y"],
+                "e": ["1", "0xff"],
+            })
+        );
     }
 
     #[test]
@@ -769,6 +3248,31 @@ mod test {
         );
     }
 
+    #[test]
+    fn fixed_bytes_and_bytes_round_trip() {
+        let values = vec![
+            Value::FixedBytes(vec![0xde, 0xad]),
+            Value::Bytes(vec![0xde, 0xad]),
+        ];
+
+        let mut expected = [0u8; 128];
+        expected[0..2].copy_from_slice(&[0xde, 0xad]); // bytes2, right padded in place
+        expected[63] = 0x40; // bytes offset
+        expected[95] = 2; // bytes length
+        expected[96..98].copy_from_slice(&[0xde, 0xad]);
+
+        let bs = Value::encode(&values);
+        assert_eq!(bs, expected);
+
+        let decoded = Value::decode_from_slice(&bs, &[Type::FixedBytes(2), Type::Bytes])
+            .expect("decode_from_slice failed");
+        assert_eq!(decoded, values);
+        assert_eq!(
+            decoded.iter().map(Value::type_of).collect::<Vec<_>>(),
+            vec![Type::FixedBytes(2), Type::Bytes]
+        );
+    }
+
     #[test]
     fn encode_fixed_array() {
         let uint1 = U256::from(57);
@@ -886,4 +3390,66 @@ mod test {
 
         assert_eq!(encoded, expected);
     }
+
+    #[test]
+    fn encode_to_hex() {
+        let values = vec![
+            Value::Uint(U256::from(0xab), 8),
+            Value::String("abc".to_string()),
+        ];
+        let tys: Vec<_> = values.iter().map(Value::type_of).collect();
+
+        let encoded = Value::encode_to_hex(&values);
+        assert_eq!(&encoded[..2], "0x");
+        assert_eq!(encoded[2..], hex::encode(Value::encode(&values)));
+        assert_eq!(
+            &encoded[2..66],
+            "00000000000000000000000000000000000000000000000000000000000000ab"
+        );
+
+        let bs = crate::hex_to_bytes(&encoded).expect("invalid hex");
+        assert_eq!(
+            Value::decode_from_slice(&bs, &tys).expect("decode_from_slice failed"),
+            values
+        );
+
+        assert_eq!(Value::encode_to_hex(&[]), "0x");
+    }
+
+    #[test]
+    fn encode_into() {
+        let values = vec![
+            Value::String("abc".to_string()),
+            Value::Uint(U256::from(5), 32),
+            Value::Array(
+                vec![Value::Bytes(vec![1, 2]), Value::Bytes(vec![3])],
+                Type::Bytes,
+            ),
+        ];
+        let expected = Value::encode(&values);
+
+        let mut buf = vec![0xff; 4];
+        Value::encode_into(&values, &mut buf);
+
+        assert_eq!(buf[..4], [0xff; 4]);
+        assert_eq!(buf[4..], expected);
+        assert_eq!(
+            Value::decode_from_slice_at(
+                &buf,
+                &[
+                    Type::String,
+                    Type::Uint(32),
+                    Type::Array(Box::new(Type::Bytes))
+                ],
+                4
+            )
+            .expect("decode failed"),
+            values
+        );
+
+        buf.clear();
+        Value::encode_into(&values, &mut buf);
+
+        assert_eq!(buf, expected);
+    }
 }
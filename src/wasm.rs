@@ -0,0 +1,67 @@
+//! JavaScript bindings built with [`wasm_bindgen`].
+//!
+//! Available with the `wasm` feature. Values cross the boundary as JSON strings so
+//! no `ethereum-types` values are exposed to JavaScript.
+
+use anyhow::Result;
+use wasm_bindgen::prelude::*;
+
+use crate::{Abi, ParseOptions};
+
+/// Decodes hex encoded function call data using the given ABI JSON.
+///
+/// Returns the JSON document built by [`Abi::decode_input_to_json`].
+#[wasm_bindgen]
+pub fn decode_input_json(abi_json: &str, calldata_hex: &str) -> Result<String, JsValue> {
+    decode_input(abi_json, calldata_hex).map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+fn decode_input(abi_json: &str, calldata_hex: &str) -> Result<String> {
+    let abi = Abi::from_str_with(abi_json, &ParseOptions::default())?;
+
+    abi.decode_input_to_json(calldata_hex)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn decode_input_to_json() {
+        let abi_json = include_str!("../examples/uniswapv3factory_abi.json");
+        let input = "0xa1671295000000000000000000000000a0b211418d87c9f5918e6213fec3b13290aa5f26000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20000000000000000000000000000000000000000000000000000000000000bb8";
+
+        let decoded = decode_input(abi_json, input).expect("decode_input failed");
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&decoded).unwrap(),
+            json!({
+                "function": "createPool",
+                "signature": "createPool(address,address,uint24)",
+                "args": [
+                    {
+                        "name": "tokenA",
+                        "type": "address",
+                        "value": "0xa0b211418d87c9f5918e6213fec3b13290aa5f26",
+                    },
+                    {
+                        "name": "tokenB",
+                        "type": "address",
+                        "value": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                    },
+                    {
+                        "name": "fee",
+                        "type": "uint24",
+                        "value": "3000",
+                    },
+                ],
+            })
+        );
+
+        assert!(decode_input(abi_json, "0x1234").is_err());
+        assert!(decode_input("{}", input).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! Property based round trip tests between encoding and decoding.
+
+use ethereum_abi::{
+    arbitrary::{any_type_and_value, any_value},
+    Abi, Function, Type, Value,
+};
+use proptest::{collection, prelude::*};
+
+// Strategy generating a list of types along with values conforming to them.
+fn any_types_and_values() -> impl Strategy<Value = (Vec<Type>, Vec<Value>)> {
+    collection::vec(any_type_and_value(), 1..6)
+        .prop_map(|tys_values| tys_values.into_iter().unzip())
+}
+
+proptest! {
+    #[test]
+    fn reencode_decoded_values((tys, values) in any_types_and_values()) {
+        let encoded = Value::encode(&values);
+        let decoded = Value::decode_from_slice(&encoded, &tys).expect("decode_from_slice failed");
+
+        prop_assert_eq!(Value::encode(&decoded), encoded);
+    }
+
+    #[test]
+    fn decode_call_data_through_abi(
+        inputs in collection::vec(any_types_and_values(), 1..4),
+        called in any::<prop::sample::Index>(),
+    ) {
+        let functions: Vec<_> = inputs
+            .iter()
+            .enumerate()
+            .map(|(i, (tys, _))| {
+                tys.iter().enumerate().fold(Function::new(format!("f{}", i)), |fun, (j, ty)| {
+                    fun.input(format!("p{}", j), ty.clone())
+                })
+            })
+            .collect();
+        let abi = Abi {
+            constructor: None,
+            functions,
+            events: vec![],
+            errors: vec![],
+            receive: None,
+            fallback: None,
+        };
+
+        let called = called.index(inputs.len());
+        let (_, values) = &inputs[called];
+
+        let mut call_data = abi.functions[called].method_id().to_vec();
+        call_data.extend(Value::encode(values));
+
+        let (fun, decoded) = abi
+            .decode_input_from_slice(&call_data)
+            .expect("decode_input_from_slice failed");
+        prop_assert_eq!(&fun.name, &abi.functions[called].name);
+        prop_assert_eq!(&decoded.values().cloned().collect::<Vec<_>>(), values);
+
+        let (fun, decoded) = abi
+            .index()
+            .decode_input_from_slice(&call_data)
+            .expect("decode_input_from_slice failed");
+        prop_assert_eq!(&fun.name, &abi.functions[called].name);
+        prop_assert_eq!(&decoded.values().cloned().collect::<Vec<_>>(), values);
+    }
+
+    #[test]
+    fn decode_truncated_encoding_does_not_panic(
+        (tys, values) in any_types_and_values(),
+        cut in any::<prop::sample::Index>(),
+    ) {
+        let encoded = Value::encode(&values);
+        let truncated = &encoded[..cut.index(encoded.len())];
+
+        let _ = Value::decode_from_slice(truncated, &tys);
+    }
+
+    #[test]
+    fn decode_nested_dynamic_arrays(
+        value in any_value(&Type::Array(Box::new(Type::Tuple(vec![
+            ("a".to_string(), Type::Array(Box::new(Type::String))),
+            ("b".to_string(), Type::FixedArray(Box::new(Type::Bytes), 2)),
+        ])))),
+    ) {
+        let ty = value.type_of();
+        let encoded = Value::encode(std::slice::from_ref(&value));
+
+        let decoded = Value::decode_from_slice(&encoded, &[ty]).expect("decode_from_slice failed");
+        prop_assert_eq!(decoded, vec![value]);
+    }
+}